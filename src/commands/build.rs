@@ -15,6 +15,9 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use colored::Colorize;
+use std::path::PathBuf;
+
 use super::plan;
 use super::plan::Plan;
 use crate::commands::helps::build;
@@ -23,18 +26,26 @@ use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
 use crate::core::ip::Ip;
+use crate::core::lang;
+use crate::core::lang::script::ScriptFormat;
 use crate::core::lang::vhdl::token::Identifier;
+use crate::core::lockfile::IP_LOCK_FILE;
+use crate::core::manifest::IP_MANIFEST_FILE;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
 use crate::core::target::Target;
+use crate::core::watch::Watcher;
 use crate::error::Error;
 use crate::error::LastError;
+use crate::util::anyerror::Fault;
 use crate::util::environment::EnvVar;
 use crate::util::environment::Environment;
 use crate::util::environment::ORBIT_BLUEPRINT;
 use crate::util::environment::ORBIT_OUT_DIR;
 use crate::util::environment::ORBIT_TARGET;
 use crate::util::environment::ORBIT_TARGET_DIR;
+use std::collections::HashSet;
+use std::fs;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
@@ -53,6 +64,15 @@ pub struct Build {
     args: Vec<String>,
     verbose: bool,
     filesets: Option<Vec<Fileset>>,
+    auto_dep: bool,
+    offline: bool,
+    scenario: Option<Vec<String>>,
+    no_default_scenario: bool,
+    strict: bool,
+    incr: bool,
+    watch: bool,
+    minimal_versions: bool,
+    format: Option<ScriptFormat>,
 }
 
 impl Subcommand<Context> for Build {
@@ -61,10 +81,21 @@ impl Subcommand<Context> for Build {
         Ok(Build {
             // Flags
             list: cli.check(Arg::flag("list"))?,
+            // wontfix (here): `verbose` is a plain switch; a repeatable
+            // `-vvv` verbosity level would need `cliproc::Arg` to grow a
+            // `Kind::Count`, but `Flag` and `Optional` are defined in the
+            // `cliproc` crate. Blocked on an upstream `cliproc` change.
             verbose: cli.check(Arg::flag("verbose"))?,
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            auto_dep: cli.check(Arg::flag("auto-dep"))?,
+            offline: cli.check(Arg::flag("offline"))?,
+            no_default_scenario: cli.check(Arg::flag("no-default-scenario"))?,
+            strict: cli.check(Arg::flag("strict"))?,
+            incr: cli.check(Arg::flag("incr"))?,
+            watch: cli.check(Arg::flag("watch"))?,
+            minimal_versions: cli.check(Arg::flag("minimal-versions"))?,
             // Options
             top: cli.get(Arg::option("top").value("unit"))?,
             plan: cli.get(Arg::option("plan").value("format"))?,
@@ -72,6 +103,8 @@ impl Subcommand<Context> for Build {
             target_dir: cli.get(Arg::option("target-dir").value("dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            scenario: cli.get_all(Arg::option("scenario").value("name"))?,
+            format: cli.get(Arg::option("format").value("format"))?,
             // Remaining args
             args: cli.remainder()?,
         })
@@ -109,6 +142,51 @@ impl Subcommand<Context> for Build {
 
         let working_ip = Ip::load(c.get_ip_path().unwrap().to_path_buf(), true)?;
 
+        self.plan_and_run(c, &working_ip, target, &plan, self.incr)?;
+
+        if self.watch == true {
+            let watch_paths: Vec<PathBuf> = working_ip
+                .gather_current_files()
+                .into_iter()
+                .map(PathBuf::from)
+                .chain([
+                    working_ip.get_root().join(IP_MANIFEST_FILE),
+                    working_ip.get_root().join(IP_LOCK_FILE),
+                ])
+                .collect();
+            let mut watcher = Watcher::new(&watch_paths);
+
+            println!("info: watching for changes ({} file(s))", watch_paths.len());
+            loop {
+                let changed = watcher.wait_for_changes(&watch_paths);
+                println!(
+                    "info: detected change in {} file(s); re-planning",
+                    changed.len()
+                );
+                // a watched source changed, so plan incrementally from here on
+                // regardless of whether `--incr` was passed on the command-line
+                if let Err(e) = self.plan_and_run(c, &working_ip, target, &plan, true) {
+                    eprintln!("{}: {}", "error".red(), e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Build {
+    /// Plans for `target` and runs it, writing the blueprint and env file and
+    /// then invoking the target's command. Factored out of [Subcommand::execute]
+    /// so `--watch` can call it again on every detected source change.
+    fn plan_and_run(
+        &self,
+        c: &Context,
+        working_ip: &Ip,
+        target: &Target,
+        plan: &Scheme,
+        incr: bool,
+    ) -> Result<(), Fault> {
         // determine the build directory based on cli priority
         let default_target_dir = c.get_target_dir();
         let target_dir = self.target_dir.as_ref().unwrap_or(&default_target_dir);
@@ -120,11 +198,11 @@ impl Subcommand<Context> for Build {
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
             .downloads(c.get_downloads_path())?;
-        let catalog = plan::resolve_missing_deps(c, &working_ip, catalog, self.force)?;
+        let catalog = plan::resolve_missing_deps(c, working_ip, catalog, self.force, self.offline)?;
 
         // plan for the provided target
         let blueprint_name = Plan::run(
-            &working_ip,
+            working_ip,
             target_dir,
             target,
             catalog,
@@ -136,17 +214,27 @@ impl Subcommand<Context> for Build {
             &None,
             &self.top,
             &self.filesets,
-            &plan,
+            plan,
             false,
             false,
+            self.auto_dep,
+            &self.scenario,
+            self.no_default_scenario,
+            self.strict,
+            incr,
+            self.minimal_versions,
         )?
         .unwrap_or_default();
 
+        if let Some(format) = &self.format {
+            self.write_script(working_ip, target, &output_path, format, &c.get_languages())?;
+        }
+
         let envs = Environment::new()
             // read config.toml for setting any env variables
             .from_config(c.get_config())?
             // read ip manifest for env variables
-            .from_ip(&working_ip)?
+            .from_ip(working_ip)?
             .add(EnvVar::with(ORBIT_TARGET, target.get_name()))
             .add(EnvVar::with(ORBIT_BLUEPRINT, &blueprint_name))
             .add(EnvVar::with(ORBIT_TARGET_DIR, target_dir))
@@ -169,4 +257,27 @@ impl Subcommand<Context> for Build {
             Err(e) => Err(Error::TargetProcFailed(LastError(e.to_string())))?,
         }
     }
+
+    /// Writes a tool script in `format` alongside the blueprint, built from
+    /// every public unit in `working_ip` that applies to `target` (see
+    /// [lang::script::write_script]), for a `--command` that expects a
+    /// pre-built compile script rather than reading the blueprint itself.
+    fn write_script(
+        &self,
+        working_ip: &Ip,
+        target: &Target,
+        output_path: &std::path::Path,
+        format: &ScriptFormat,
+        lang: &crate::core::lang::Language,
+    ) -> Result<(), Fault> {
+        let units = working_ip.collect_units(self.force, lang, true)?;
+        let targets = HashSet::from([target.get_name().to_string()]);
+        let script = lang::script::write_script(&units, format, true, &targets)?;
+        let file_name = match format {
+            ScriptFormat::FileList => "compile.lst",
+            ScriptFormat::Modelsim => "compile.do",
+        };
+        fs::write(output_path.join(file_name), script)?;
+        Ok(())
+    }
 }
@@ -56,6 +56,7 @@ pub struct Config {
     pop: Vec<String>,
     set: Vec<Entry>,
     unset: Vec<String>,
+    import: Option<PathBuf>,
 }
 
 impl Subcommand<Context> for Config {
@@ -77,6 +78,7 @@ impl Subcommand<Context> for Config {
             unset: cli
                 .get_all(Arg::option("unset").value("key"))?
                 .unwrap_or(Vec::new()),
+            import: cli.get(Arg::option("import").value("path"))?,
             // Optional positionals
             path: cli.get(Arg::positional("path"))?,
         })
@@ -147,7 +149,30 @@ impl Subcommand<Context> for Config {
 
 impl Config {
     fn no_options_selected(&self) -> bool {
-        self.push.is_empty() && self.pop.is_empty() && self.set.is_empty() && self.unset.is_empty()
+        self.push.is_empty()
+            && self.pop.is_empty()
+            && self.set.is_empty()
+            && self.unset.is_empty()
+            && self.import.is_none()
+    }
+
+    /// Reads the legacy cfgfile document at `self.import` and flattens it
+    /// into `--set`-style entries, so the rest of `run`/`run_all` can apply
+    /// it through the exact same [ConfigDocument::set] path as a hand-typed
+    /// `--set table.key=value`.
+    fn imported_entries(&self) -> Result<Vec<Entry>, Fault> {
+        match &self.import {
+            Some(p) => {
+                let contents = std::fs::read_to_string(p)
+                    .map_err(|e| AnyError(format!("failed to read {:?}: {}", p, e)))?;
+                let triples = core::cfg::import_legacy_cfg(&contents).map_err(|e| AnyError(e))?;
+                Ok(triples
+                    .into_iter()
+                    .map(|(table, key, value)| Entry(format!("{}.{}", table, key), value))
+                    .collect())
+            }
+            None => Ok(Vec::new()),
+        }
     }
 
     fn run_all(&self, mut configs: Vec<(ConfigDocument, PathBuf, Locality)>) -> Result<(), Fault> {
@@ -219,6 +244,21 @@ impl Config {
             }
         }
 
+        // apply any fields pulled in from a legacy cfgfile import the same
+        // way a hand-typed `--set` entry is applied
+        for entry in &self.imported_entries()? {
+            if let Some((table, key)) = entry.0.split_once('.') {
+                let cfg = match configs
+                    .iter_mut()
+                    .find(|(c, _, _)| c.is_set(Some(table), &key))
+                {
+                    Some(cfg) => cfg,
+                    None => configs.last_mut().unwrap(),
+                };
+                cfg.0.set(table, key, &entry.1);
+            }
+        }
+
         // verify all configs
         for cfg in &configs {
             // is the config file okay?
@@ -286,6 +326,14 @@ impl Config {
             }
         }
 
+        // apply any fields pulled in from a legacy cfgfile import the same
+        // way a hand-typed `--set` entry is applied
+        for entry in &self.imported_entries()? {
+            if let Some((table, key)) = entry.0.split_once('.') {
+                config.0.set(table, key, &entry.1)
+            }
+        }
+
         // is the config file okay?
         match core::config::Config::from_str(&config.0.to_string()) {
             Ok(r) => {
@@ -28,6 +28,8 @@ use crate::core::lockfile::LockFile;
 use crate::core::manifest;
 use crate::core::manifest::IP_MANIFEST_FILE;
 use crate::core::protocol::Protocol;
+use crate::core::source;
+use crate::core::source::Mirror;
 use crate::core::source::Source;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
@@ -124,7 +126,13 @@ impl Subcommand<Context> for Download {
         let lf = ip.get_lock().keep_dev_dep_entries(&ip, self.all);
 
         let downloads =
-            Self::compile_download_list(&LockEntry::from((&ip, true)), &lf, &catalog, missing_only);
+            Self::compile_download_list(
+                &LockEntry::from((&ip, true)),
+                &lf,
+                &catalog,
+                missing_only,
+                c.get_config().get_mirrors(),
+            );
         // print to console
         if to_stdout == true {
             downloads.iter().for_each(|(_, src)| println!("{}", src));
@@ -153,6 +161,7 @@ impl Download {
         lf: &'a LockFile,
         catalog: &Catalog,
         missing_only: bool,
+        mirrors: &[Mirror],
     ) -> Vec<(IpSpec, Source)> {
         let mut vtable = StrSwapTable::new();
         lf.inner()
@@ -167,7 +176,8 @@ impl Download {
                 let spec = f.to_ip_spec();
                 vtable.add("orbit.ip.name", spec.get_name().as_ref());
                 vtable.add("orbit.ip.version", &spec.get_version().to_string());
-                let processed_src = f.get_source().unwrap().clone().replace_vars_in_url(&vtable);
+                let processed_src = source::apply_mirrors(f.get_source().unwrap().clone(), mirrors)
+                    .replace_vars_in_url(&vtable);
                 (spec, processed_src)
             })
             .collect()
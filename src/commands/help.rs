@@ -141,6 +141,11 @@ impl Help {
     }
 }
 
+// wontfix (here): a generated, two-column `--help` screen derived from a
+// `desc` on each `Arg` requires `Positional`, `Flag`, and `Optional` to grow
+// that field, but those types are defined in the `cliproc` crate, not this
+// repo. Blocked on an upstream `cliproc` change; every subcommand's help
+// text (like the one below) stays hand-written until then.
 const HELP: &str = "\
 Read in-depth documentation on Orbit topics.
 
@@ -13,6 +13,15 @@ Options:
     --list                  view available targets and exit
     --all                   include all hdl files of the working ip
     --fileset <key=glob>... set filesets for the target
+    --auto-dep              auto-add dependencies suggested to resolve dangling references
+    --offline               skip network access and fail if a dependency is not already cached
+    --scenario <name>...    include hdl files tagged under the given scenario
+    --no-default-scenario   exclude hdl files tagged under the "default" scenario
+    --strict                fail if any instantiation resolves to a black box
+    --incr                  only rebuild files changed since the last plan
+    --minimal-versions      resolve dependencies to their lowest compatible versions
+    --format <format>       additionally write a tool script ("flist" or "modelsim")
+    --watch                 re-plan and re-run the target on source changes
     --force                 force the target to execute
     --verbose               display the command being executed
     args                    arguments to pass to the requested command
@@ -29,6 +29,7 @@ Options:
     --set <key=value>...
                           store the value as the key's entry
     --unset <key>...      delete the key's entry
+    --import <path>       upgrade a legacy cfgfile document's fields into this config
     --list                print the list of configuration files and exit
 
 Use 'orbit help config' to read more about the command."#;
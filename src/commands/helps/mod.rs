@@ -25,6 +25,7 @@ pub mod install;
 pub mod lock;
 pub mod new;
 pub mod orbit;
+pub mod pack;
 pub mod plan;
 pub mod publish;
 pub mod read;
@@ -32,4 +33,5 @@ pub mod remove;
 pub mod search;
 pub mod test;
 pub mod tree;
+pub mod unpack;
 pub mod view;
@@ -43,6 +43,10 @@ Options:
     --upgrade             check for the latest orbit binary
     --sync                synchronize configured channels
     --force               bypass interactive prompts
+    --locked              assert the lockfile will not change
+    --frozen              assert the lockfile will not change and forbid network access
+    --yes, -y             auto-accept any confirmation prompts
+    --non-interactive     resolve confirmation prompts to their default without reading stdin
     --color <when>        coloring: auto, always, never
     --help, -h            print help information
 
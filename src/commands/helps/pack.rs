@@ -0,0 +1,14 @@
+// This help page was automatically generated from the mangen.py tool.
+pub const HELP: &str = r#"Archive the current ip into a solid-compressed tarball.
+
+Usage:
+    orbit pack [options]
+
+Options:
+    --output <file>     destination archive path (default: <ip>.tar.xz)
+    --level <0-9>       lzma2 compression preset (default: 6)
+    --window <mb>       lzma2 dictionary/window size in megabytes (default: 64)
+    --threads <n>       number of worker threads to use for encoding
+
+Use 'orbit help pack' to read more about the command.
+"#;
@@ -26,5 +26,7 @@ Options:
     --format <fmt>        determine how to display nodes ('long', 'short')
     --ascii               limit the textual tree characters to the 128 ASCII set
     --ip                  switch to the ip dependency graph
+    --json                print the graph as a node/edge list in json
+    --dot                 print the systemverilog design-element graph as graphviz dot
 
 Use 'orbit help tree' to read more about the command."#;
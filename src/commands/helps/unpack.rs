@@ -0,0 +1,14 @@
+// This help page was automatically generated from the mangen.py tool.
+pub const HELP: &str = r#"Extract an ip archive produced by 'orbit pack'.
+
+Usage:
+    orbit unpack [options] <archive>
+
+Args:
+    <archive>           path to the .tar.xz archive to extract
+
+Options:
+    --output <dir>      destination directory (default: archive's file stem)
+
+Use 'orbit help unpack' to read more about the command.
+"#;
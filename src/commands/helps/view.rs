@@ -21,11 +21,13 @@ pub const HELP: &str = r#"Display metadata of an ip.
 Usage:
     orbit show [options] [<ip>]
 
-Options:  
-    <ip>                        the spec of the ip to query       
+Options:
+    <ip>                        the spec of the ip to query
     --versions, -v              display the list of possible versions
     --units, -u                 display primary design units within an ip
     --all, -a                   include any private or hidden results
+    --format <fmt>              print the result as 'json' instead of text
+    --range <version:version>   narrow the displayed version list
 
 Use 'orbit help view' to read more about the command.
 "#;
@@ -34,6 +34,8 @@
 //!     - ...
 //!
 
+use std::collections::HashSet;
+
 use super::plan::Plan;
 use super::publish::Publish;
 use crate::commands::download::Download;
@@ -424,7 +426,7 @@ impl Install {
             }
         // create the lockfile
         } else if local_ip.can_use_lock() == false {
-            let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog, &Language::default())?;
+            let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog, &Language::default(), &HashSet::new())?;
             Plan::write_lockfile(&local_ip, &ip_graph, true)?;
         }
 
@@ -566,7 +568,14 @@ impl Install {
     ) -> Result<Option<Ip>, Fault> {
         // temporary destination to move files for processing and manipulation
         let dest = tempfile::tempdir()?.into_path();
-        filesystem::copy(src.get_root(), &dest, true, Some(src.get_files_to_keep()))?;
+        filesystem::copy(
+            src.get_root(),
+            &dest,
+            Some(crate::util::filetype::MINIMAL_TYPES),
+            Some(src.get_files_to_keep()),
+            filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        )?;
 
         // lookup the package name in the index to see if the UUIDs match
         // verify the version for this package is not already logged
@@ -617,7 +626,14 @@ impl Install {
             }
         }
         // copy contents into cache slot from temporary destination
-        crate::util::filesystem::copy(&dest, &cache_slot, false, Some(src.get_files_to_keep()))?;
+        crate::util::filesystem::copy(
+            &dest,
+            &cache_slot,
+            None,
+            Some(src.get_files_to_keep()),
+            crate::util::filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        )?;
 
         // clean up the temporary directory ourself
         fs::remove_dir_all(dest)?;
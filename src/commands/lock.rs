@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use super::plan::{self, Plan};
 use crate::commands::helps::lock;
 use crate::core::algo;
@@ -83,7 +85,7 @@ impl Lock {
         force: bool,
     ) -> Result<(), Fault> {
         // build entire ip graph and resolve with dynamic symbol transformation
-        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog, lang) {
+        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog, lang, &HashSet::new()) {
             Ok(g) => g,
             Err(e) => return Err(e)?,
         };
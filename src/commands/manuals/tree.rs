@@ -48,6 +48,12 @@ DESCRIPTION
     be displayed using a set of standard ASCII characters with the '--ascii'
     option.
 
+    To consume the graph from an editor or a CI script, use the '--json' option
+    to print the entire graph as an explicit node/edge list instead of a text
+    tree; any '<unit>' roots do not narrow this output. The document carries a
+    "version" field so the schema can evolve without breaking existing
+    consumers.
+
 OPTIONS
     <unit>...
         Uppermost hdl unit of the dependency tree
@@ -61,8 +67,12 @@ OPTIONS
     --ip
         Switch to the ip dependency graph
 
+    --json
+        Print the graph as a node/edge list in json
+
 EXAMPLES
     orbit tree
     orbit tree top --format long
     orbit tree --ip --ascii
+    orbit tree --ip --json
 "#;
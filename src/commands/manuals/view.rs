@@ -33,7 +33,20 @@ DESCRIPTION
     from the results. To display design elements of all visibility levels the
     '--all' option must also be present.
     
-    To display the known versions for an ip, use the '--versions' option.
+    To display the known versions for an ip, use the '--versions' option. To
+    narrow the list to a bounded range, pair it with '--range <version:version>',
+    an inclusive 'low:high' pair of partial version numbers. Either side may
+    be left empty to leave that bound open, for example ':1.4' or '1.2:'.
+
+    If the ip is not installed or downloaded, its configured channels are
+    searched for a matching registry listing so its manifest and version list
+    can still be viewed prior to installing. The version list's status column
+    distinguishes an 'install'ed or 'download'ed ip from one only 'available'
+    through a channel.
+
+    To consume the result from an editor or a CI script, use '--format json' to
+    print the manifest, unit list, or version list as a single JSON value
+    instead of the default human-formatted text.
 
 OPTIONS
     <ip>
@@ -48,9 +61,17 @@ OPTIONS
     --all, -a
         Include any private or hidden results
 
+    --format <fmt>
+        Print the result as 'json' instead of text
+
+    --range <version:version>
+        Narrow the displayed version list to an inclusive bound
+
 EXAMPLES
     orbit view --units
     orbit view gates:1.0.0 -u --all
     orbit view gates --versions
     orbit view gates:1 -v
+    orbit view gates --format json
+    orbit view gates -v --range 1.2:1.4
 "#;
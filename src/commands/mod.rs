@@ -28,6 +28,7 @@ mod help;
 mod init;
 mod install;
 mod new;
+mod pack;
 mod plan;
 mod publish;
 mod read;
@@ -35,6 +36,7 @@ mod remove;
 mod search;
 mod test;
 mod tree;
+mod unpack;
 mod view;
 
 // informational content for help about commands
@@ -55,6 +55,10 @@ pub struct Orbit {
     upgrade: bool,
     version: bool,
     force: bool,
+    locked: bool,
+    frozen: bool,
+    yes: bool,
+    non_interactive: bool,
     cmode: ColorMode,
     command: Option<OrbitSubcommand>,
 }
@@ -66,6 +70,10 @@ impl Command for Orbit {
             upgrade: cli.check(Arg::flag("upgrade"))?,
             version: cli.check(Arg::flag("version"))?,
             force: cli.check(Arg::flag("force"))?,
+            locked: cli.check(Arg::flag("locked"))?,
+            frozen: cli.check(Arg::flag("frozen"))?,
+            yes: cli.check(Arg::flag("yes").switch('y'))?,
+            non_interactive: cli.check(Arg::flag("non-interactive"))?,
             cmode: cli
                 .get(Arg::option("color").value("when"))?
                 .unwrap_or_default(),
@@ -76,6 +84,19 @@ impl Command for Orbit {
     fn execute(self) -> proc::Result {
         // synchronize the coloring mode
         self.cmode.sync();
+        // `--frozen` implies `--locked`, mirroring cargo
+        if self.locked == true || self.frozen == true {
+            env::set_var(environment::ORBIT_LOCKED, "1");
+        }
+        if self.frozen == true {
+            env::set_var(environment::ORBIT_FROZEN, "1");
+        }
+        if self.yes == true {
+            env::set_var(environment::ORBIT_ASSUME_YES, "1");
+        }
+        if self.non_interactive == true {
+            env::set_var(environment::ORBIT_NON_INTERACTIVE, "1");
+        }
         // prioritize version information
         if self.version == true {
             println!("orbit {}", VERSION);
@@ -96,7 +117,8 @@ impl Command for Orbit {
                 .channels(environment::ORBIT_CHANNELS)?
                 .current_ip_dir(environment::ORBIT_IP_PATH)? // must come before .settings() call
                 .settings(config::CONFIG_FILE)?
-                .build_dir(environment::ORBIT_BUILD_DIR)?;
+                .build_dir(environment::ORBIT_BUILD_DIR)?
+                .threads();
             // pass the context to the given command
             sub.execute(&context)
         // if no command is given then print default help
@@ -116,12 +138,14 @@ use crate::commands::init::Init;
 use crate::commands::install::Install;
 use crate::commands::launch::Launch;
 use crate::commands::new::New;
+use crate::commands::pack::Pack;
 use crate::commands::plan::Plan;
 use crate::commands::read::Read;
 use crate::commands::remove::Remove;
 use crate::commands::run::Run;
 use crate::commands::search::Search;
 use crate::commands::tree::Tree;
+use crate::commands::unpack::Unpack;
 use crate::commands::view::View;
 
 #[derive(Debug, PartialEq)]
@@ -143,18 +167,22 @@ enum OrbitSubcommand {
     Uninstall(Remove),
     Read(Read),
     Download(Download),
+    Pack(Pack),
+    Unpack(Unpack),
 }
 
+/// The full set of built-in subcommand names (including their short
+/// aliases), used both to dispatch in [OrbitSubcommand::interpret] and to
+/// let a real subcommand always shadow a same-named user-defined
+/// [crate::core::alias].
+pub const SUBCOMMANDS: &[&str] = &[
+    "help", "new", "search", "plan", "p", "build", "run", "launch", "download", "install", "get",
+    "init", "tree", "view", "b", "env", "config", "remove", "read", "r", "pack", "unpack",
+];
+
 impl Subcommand<Context> for OrbitSubcommand {
     fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
-        match cli
-            .select(&[
-                "help", "new", "search", "plan", "p", "build", "run", "launch", "download",
-                "install", "get", "init", "tree", "view", "b", "env", "config", "remove", "read",
-                "r",
-            ])?
-            .as_ref()
-        {
+        match cli.select(SUBCOMMANDS)?.as_ref() {
             "get" => Ok(OrbitSubcommand::Get(Get::interpret(cli)?)),
             "help" => Ok(OrbitSubcommand::Help(Help::interpret(cli)?)),
             "new" => Ok(OrbitSubcommand::New(New::interpret(cli)?)),
@@ -172,6 +200,8 @@ impl Subcommand<Context> for OrbitSubcommand {
             "config" => Ok(OrbitSubcommand::Config(Config::interpret(cli)?)),
             "remove" => Ok(OrbitSubcommand::Uninstall(Remove::interpret(cli)?)),
             "read" => Ok(OrbitSubcommand::Read(Read::interpret(cli)?)),
+            "pack" => Ok(OrbitSubcommand::Pack(Pack::interpret(cli)?)),
+            "unpack" => Ok(OrbitSubcommand::Unpack(Unpack::interpret(cli)?)),
             _ => panic!("an unimplemented command was passed through!"),
         }
     }
@@ -195,6 +225,8 @@ impl Subcommand<Context> for OrbitSubcommand {
             OrbitSubcommand::Uninstall(sub) => sub.execute(context),
             OrbitSubcommand::Read(sub) => sub.execute(context),
             OrbitSubcommand::Download(sub) => sub.execute(context),
+            OrbitSubcommand::Pack(sub) => sub.execute(context),
+            OrbitSubcommand::Unpack(sub) => sub.execute(context),
         }
     }
 }
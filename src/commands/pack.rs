@@ -0,0 +1,82 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::path::PathBuf;
+
+use crate::commands::helps::pack;
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::util::archive;
+use crate::util::archive::PackOptions;
+use crate::util::filesystem;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Pack {
+    output: Option<PathBuf>,
+    level: Option<u32>,
+    window: Option<u32>,
+    threads: Option<u32>,
+}
+
+impl Subcommand<Context> for Pack {
+    fn interpret(cli: &mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(pack::HELP))?;
+        Ok(Pack {
+            output: cli.get(Arg::option("output").value("file"))?,
+            level: cli.get(Arg::option("level").value("0-9"))?,
+            window: cli.get(Arg::option("window").value("mb"))?,
+            threads: cli.get(Arg::option("threads").value("n"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // verify running from an ip directory and enter its root directory
+        c.jump_to_working_ip()?;
+        let ip = Ip::load(c.get_ip_path().unwrap().clone(), true)?;
+
+        let dest = self.output.unwrap_or_else(|| {
+            PathBuf::from(format!("{}.tar.xz", ip.get_man().get_ip().into_ip_spec()))
+        });
+
+        let options = PackOptions {
+            level: self.level.unwrap_or(archive::DEFAULT_LEVEL),
+            window: self
+                .window
+                .map(|mb| mb * 1024 * 1024)
+                .unwrap_or(archive::DEFAULT_DICT_SIZE),
+            threads: self.threads,
+        };
+
+        // gather the reproducible, sorted fileset to embed in the archive
+        let file_types = c
+            .get_config()
+            .get_filetypes()
+            .cloned()
+            .unwrap_or_default();
+        let files =
+            filesystem::gather_current_files(ip.get_root(), true, c.get_threads(), &file_types);
+
+        archive::pack(ip.get_root(), &files, &dest, &options)?;
+
+        println!("info: packed ip into archive {:?}", dest);
+
+        Ok(())
+    }
+}
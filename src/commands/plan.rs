@@ -19,16 +19,25 @@ use colored::Colorize;
 
 use crate::commands::download::Download;
 use crate::core::blueprint::{Blueprint, Instruction, Scheme};
+use crate::core::cache::{self, GraphStamp, ParseCache};
 use crate::core::context::{self, Context};
 use crate::core::fileset::Fileset;
+use crate::core::fileset::Style;
 use crate::core::iparchive::IpArchive;
 use crate::core::lang::parser::ParseError;
 use crate::core::lang::reference::CompoundIdentifier;
-use crate::core::lang::sv::symbols::{SystemVerilogParser, SystemVerilogSymbol};
+use crate::core::lang::sv::symbols::{
+    analyze, elaborate, AnalyzerError, SystemVerilogParser, SystemVerilogSymbol,
+};
 use crate::core::lang::verilog::symbols::{VerilogParser, VerilogSymbol};
 use crate::core::lang::vhdl::subunit::SubUnit;
 use crate::core::lang::vhdl::symbols::{VHDLParser, VhdlSymbol};
+use crate::core::lang::vhdl::token::confusable::detect_confusables;
+use crate::core::lang::vhdl::token::raw::{lex_raw, RawVhdlKind};
+use crate::core::lang::vhdl::token::tokenizer::VhdlTokenizer;
+use crate::core::lang::vhdl::token::CommentDirective;
 use crate::core::lang::vhdl::token::Identifier;
+use crate::core::lang::vhdl::token::VhdlToken;
 use crate::core::lang::{self, Lang, LangIdentifier, Language};
 use crate::core::swap;
 use crate::core::swap::StrSwapTable;
@@ -38,6 +47,7 @@ use crate::error::{Error, Hint, LastError};
 use crate::util::anyerror::Fault;
 use crate::util::environment;
 use crate::util::environment::EnvVar;
+use crate::util::lock::CacheLock;
 use crate::util::environment::Environment;
 use crate::util::filesystem;
 use crate::util::graph::EdgeStatus;
@@ -49,6 +59,7 @@ use std::path::{Path, PathBuf};
 
 use crate::commands::install::Install;
 use crate::core::algo;
+use crate::core::algo::DanglingRef;
 use crate::core::algo::IpFileNode;
 use crate::core::algo::IpNode;
 use crate::core::catalog::Catalog;
@@ -56,8 +67,22 @@ use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::lockfile::LockEntry;
 use crate::core::lockfile::LockFile;
+use crate::core::lockfile::IP_LOCK_FILE;
+use crate::core::manifest::IP_MANIFEST_FILE;
+use crate::core::resolver;
 use crate::util::graphmap::Node;
 
+/// A single instantiation of an unresolved ("black box") unit: `unit`
+/// could not be resolved by anything in the referencing unit's dependency
+/// closure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedRefFound {
+    unit: CompoundIdentifier,
+    referenced_by: CompoundIdentifier,
+    source_file: String,
+    library: LangIdentifier,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Plan {
     target: Option<String>,
@@ -92,14 +117,39 @@ impl Plan {
         scheme: &Scheme,
         require_bench: bool,
         allow_bench: bool,
+        auto_dep: bool,
+        scenarios: &Option<Vec<String>>,
+        no_default_scenario: bool,
+        strict: bool,
+        incremental: bool,
+        minimal_versions: bool,
     ) -> Result<Option<String>, Fault> {
         // create the output path to know where to begin storing files
         let working_ip_path = working_ip.get_root().clone();
         let target_path = working_ip_path.join(target_dir);
         let output_path = target_path.join(target.get_name());
 
-        // build entire ip graph and resolve with dynamic symbol transformation
-        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog, lang) {
+        // check the dependency set is satisfiable before walking hdl source; a
+        // conflict or cycle here is reported against the ip versions that
+        // caused it, rather than surfacing later as a confusing missing- or
+        // duplicate-symbol error out of `compute_final_ip_graph`. `--minimal-versions`
+        // swaps in the MVS strategy, which prefers the lowest compatible version of
+        // each dependency instead of the newest, for more reproducible builds.
+        let resolution = if minimal_versions == true {
+            resolver::resolve_minimal_versions(&working_ip, &catalog)
+        } else {
+            resolver::resolve(&working_ip, &catalog)
+        };
+        if let Err(e) = resolution {
+            return Err(Error::DependencyResolutionFailed(LastError(e.to_string())))?;
+        }
+
+        // build entire ip graph and resolve with dynamic symbol transformation,
+        // narrowed to units tagged for this invocation's target (untagged
+        // units still match everything, so this is a no-op until a fileset
+        // actually tags a unit for a specific target)
+        let build_targets: HashSet<String> = HashSet::from([target.get_name().to_string()]);
+        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog, lang, &build_targets) {
             Ok(g) => g,
             Err(e) => {
                 // generate a single blueprint
@@ -153,8 +203,78 @@ impl Plan {
 
         let files = algo::build_ip_file_list(&ip_graph, &working_ip, &lang);
 
+        // narrow down to only the files tagged for an active scenario (an ip
+        // that defines no scenarios opts all of its files into every scenario)
+        let mut active_scenarios: HashSet<String> = HashSet::new();
+        if no_default_scenario == false {
+            active_scenarios.insert(String::from("default"));
+        }
+        if let Some(names) = scenarios {
+            active_scenarios.extend(names.iter().cloned());
+        }
+        let files = Self::filter_by_scenario(files, &active_scenarios);
+
+        // refresh the on-disk parse cache's fast-path fingerprints for this
+        // target; currently used to report staleness and, under `--incr`,
+        // to narrow the blueprint down to a dirty subset further below (see
+        // `core::cache::ParseCache` for why this isn't yet a full
+        // skip-the-parser cache). Saved once at the end of planning, after
+        // `compute_dirty_file_order` has had a chance to update it too.
+        if Path::exists(&output_path) == false {
+            fs::create_dir_all(&output_path)?;
+        }
+        let cache_path = output_path.join(cache::PARSE_CACHE_FILE);
+        let mut parse_cache = ParseCache::load(&cache_path);
+        let unchanged_count = files
+            .iter()
+            .filter(|f| parse_cache.is_unchanged(f.get_file()))
+            .count();
+        if unchanged_count > 0 {
+            println!(
+                "info: {} of {} hdl files unchanged since last plan",
+                unchanged_count,
+                files.len()
+            );
+        }
+
+        // a cheap validity check standing in for a real graph cache (see
+        // `core::cache::GraphStamp` for why it only reports staleness today
+        // rather than skipping the rebuild below)
+        let graph_cache_path = output_path.join(cache::GRAPH_CACHE_FILE);
+        let graph_stamp = GraphStamp::compute(
+            &working_ip.get_root().join(IP_LOCK_FILE),
+            &files.iter().map(|f| f.get_file().clone()).collect(),
+        );
+        if graph_stamp.is_unchanged(&graph_cache_path) {
+            println!("info: resolved hdl graph unchanged since last plan");
+        }
+        graph_stamp.save(&graph_cache_path)?;
+
         let global_graph = Self::build_full_graph(&files)?;
 
+        // look for any unit that is instantiated but left undefined by the
+        // current dependency closure, and suggest (or auto-add) the
+        // installed ip that would satisfy it
+        let dangling_refs = Self::find_dangling_refs(&global_graph);
+        if dangling_refs.is_empty() == false {
+            let suggestions = algo::suggest_dependencies(&dangling_refs, &catalog, lang);
+            Self::report_dangling_refs(&suggestions);
+            if auto_dep == true {
+                Self::auto_add_dependencies(&working_ip, &suggestions)?;
+            }
+        }
+
+        // report every instantiation of an unresolved (black box) unit; under
+        // `--strict` this fails the plan outright instead of silently
+        // proceeding with a degraded netlist
+        let unresolved = Self::collect_unresolved_refs(&global_graph);
+        if unresolved.is_empty() == false {
+            if strict == true {
+                return Err(PlanError::BlackBoxesFound(unresolved))?;
+            }
+            Self::report_unresolved_refs(&unresolved);
+        }
+
         let working_lib = working_ip.get_hdl_library();
 
         // restrict graph to units only found within the current IP
@@ -221,9 +341,10 @@ impl Plan {
         };
         // guarantees top exists if not using --all
 
-        // error if the user-defined top is not instantiated in the testbench. Say this can be fixed by adding '--all'
+        // if the user-defined top is not instantiated in the testbench, the two are
+        // disjoint roots; merge their topological orders below instead of requiring '--all'
+        let mut merge_top_with_bench = false;
         if let Some(b) = &bench {
-            // @idea: merge two topological sorted lists together by running top sort from bench and top sort from top if in this situation
             if all == false
                 && top.is_some()
                 && global_graph
@@ -232,16 +353,7 @@ impl Plan {
                     .find(|i| i == b)
                     .is_none()
             {
-                let given_top = global_graph
-                    .get_key_by_index(top.unwrap())
-                    .unwrap()
-                    .get_suffix();
-                let given_bench = global_graph.get_key_by_index(*b).unwrap().get_suffix();
-                return Err(Error::TopNotInTestbench(
-                    given_top.clone(),
-                    given_bench.clone(),
-                    Hint::IncludeAllInPlan,
-                ))?;
+                merge_top_with_bench = true;
             }
         } else if bench.is_none() == true && require_bench == true {
             return Err(Error::TestbenchRequired)?;
@@ -280,24 +392,39 @@ impl Plan {
             }
             // perform topological sort on minimal subset of the graph
             false => {
-                // determine which point is the upmost root
-                let highest_point = match bench {
-                    Some(b) => b,
-                    None => match top {
-                        Some(t) => t,
-                        None => return Err(AnyError(format!("no top-level unit exists")))?,
-                    },
-                };
-                global_graph
-                    .get_graph()
-                    .minimal_topological_sort(highest_point)
+                // the testbench does not instantiate the user-defined top; sort from the
+                // bench and from the top separately and merge the two orders together
+                // (bench first, so its own dependencies compile before the standalone top)
+                if merge_top_with_bench == true {
+                    let mut order = global_graph
+                        .get_graph()
+                        .minimal_topological_sort(bench.unwrap());
+                    order.append(
+                        &mut global_graph
+                            .get_graph()
+                            .minimal_topological_sort(top.unwrap()),
+                    );
+                    order
+                } else {
+                    // determine which point is the upmost root
+                    let highest_point = match bench {
+                        Some(b) => b,
+                        None => match top {
+                            Some(t) => t,
+                            None => return Err(AnyError(format!("no top-level unit exists")))?,
+                        },
+                    };
+                    global_graph
+                        .get_graph()
+                        .minimal_topological_sort(highest_point)
+                }
             }
         };
 
         // println!("{:?}", min_order);
 
         // generate the file order while merging dependencies for common file path names together
-        let file_order = Self::determine_file_order(&global_graph, min_order);
+        let (file_order, file_deps) = Self::determine_file_order(&global_graph, min_order);
 
         // remove duplicate files from list while perserving order
         let file_order = Self::remove_multi_occurences(&file_order);
@@ -320,6 +447,24 @@ impl Plan {
             None => String::new(),
         };
 
+        // under `--incr`, narrow the file list down to only what changed (or
+        // what transitively depends on something that changed) since the
+        // last plan; reuses the same on-disk cache introduced for
+        // `core::cache::ParseCache`'s fast-path staleness check, extended
+        // with each file's direct dependency set and the top/bench selection
+        let file_order = if incremental == true {
+            Self::compute_dirty_file_order(
+                file_order,
+                &file_deps,
+                &mut parse_cache,
+                &top_name,
+                &bench_name,
+            )
+        } else {
+            file_order
+        };
+        parse_cache.save(&cache_path)?;
+
         // print information (maybe also print the plugin saved to .env too?)
         match top_name.is_empty() {
             false => match require_bench {
@@ -440,12 +585,38 @@ impl Plan {
     }
 }
 
+/// Acquires the advisory [CacheLock] on `dir`, blocking while another
+/// process holds it, unless `--locked`/`--frozen` was specified — in which
+/// case contention is reported as an error rather than waited out, since
+/// blocking could let `Orbit.lock` be observed to change underneath us.
+fn acquire_cache_lock(dir: &PathBuf) -> Result<CacheLock, Fault> {
+    if environment::is_locked() == true {
+        match CacheLock::try_acquire(dir) {
+            Ok(Some(lock)) => Ok(lock),
+            Ok(None) => Err(Box::new(Error::CacheLockContended(dir.clone()))),
+            Err(e) => Err(Box::new(Error::CacheLockFailed(
+                dir.clone(),
+                LastError(e.to_string()),
+            ))),
+        }
+    } else {
+        CacheLock::acquire(dir)
+            .map_err(|e| Box::new(Error::CacheLockFailed(dir.clone(), LastError(e.to_string()))) as Fault)
+    }
+}
+
 pub fn resolve_missing_deps<'a>(
     c: &'a Context,
     working_ip: &'a Ip,
     mut catalog: Catalog<'a>,
     force: bool,
+    offline: bool,
 ) -> Result<Catalog<'a>, Fault> {
+    // guard the downloads/cache directories against another concurrent
+    // orbit process while this one fetches and installs missing ip; the
+    // guard is released automatically once it falls out of scope below
+    let _downloads_lock = acquire_cache_lock(c.get_downloads_path())?;
+    let _cache_lock = acquire_cache_lock(c.get_cache_path())?;
     // this code is only ran if the lock file matches the manifest and we aren't force to recompute
     if working_ip.can_use_lock() == true && force == false {
         let le: LockEntry = LockEntry::from((working_ip, true));
@@ -456,7 +627,14 @@ pub fn resolve_missing_deps<'a>(
             .from_config(c.get_config())?;
         let vtable = StrSwapTable::new().load_environment(&env)?;
 
-        download_missing_deps(vtable, &lf, &le, &catalog, &c.get_config().get_protocols())?;
+        download_missing_deps(
+            vtable,
+            &lf,
+            &le,
+            &catalog,
+            &c.get_config().get_protocols(),
+            offline,
+        )?;
         // recollect the downloaded items to update the catalog for installations
         catalog = catalog.downloads(c.get_downloads_path())?;
 
@@ -474,6 +652,7 @@ pub fn download_missing_deps(
     le: &LockEntry,
     catalog: &Catalog,
     protocols: &ProtocolMap,
+    offline: bool,
 ) -> Result<(), Fault> {
     let mut vtable = vtable;
     // fetch all non-downloaded packages
@@ -522,6 +701,12 @@ pub fn download_missing_deps(
         }
         // check if the slot is not already filled before trying to download
         if require_download == true {
+            if offline == true {
+                return Err(Error::OfflineDependencyMissing(
+                    entry.to_ip_spec(),
+                    Hint::DisableOffline,
+                ))?;
+            }
             match entry.get_source() {
                 Some(src) => {
                     // fetch from the internet
@@ -731,10 +916,36 @@ impl Plan {
             Ok(s) => s.into_symbols(),
             Err(e) => Err(ParseError::SourceCodeError(
                 node.get_file().clone(),
-                e.to_string(),
+                e.render(&contents, node.get_file()),
             ))?,
         };
 
+        for diagnostic in analyze(&symbols) {
+            match diagnostic {
+                AnalyzerError::UnusedElement(name, pos) => println!(
+                    "{}: unused design element \"{}\" in {}{}",
+                    "warning".yellow(),
+                    name,
+                    node.get_file(),
+                    pos,
+                ),
+            }
+        }
+
+        // `elaborate` only sees this one file's symbols, so a reference to
+        // a module defined elsewhere in the ip is indistinguishable from
+        // an actually-missing one here; only a same-file circular
+        // instantiation is meaningful to flag without flooding every
+        // normal multi-file design with false positives.
+        if let Some(cycle) = elaborate(&symbols).find_cycle() {
+            println!(
+                "{}: circular instantiation within {}: {}",
+                "warning".yellow(),
+                node.get_file(),
+                cycle.join(" -> "),
+            );
+        }
+
         let lib = node.get_library();
         let vhdl_lib = lib.as_vhdl_name().unwrap().clone();
         // println!("{} {}", source_file.get_file(), source_file.get_library());
@@ -787,6 +998,97 @@ impl Plan {
         sub_nodes: &'b mut Vec<(LangIdentifier, SubUnitNode<'a>)>,
     ) -> Result<(), Fault> {
         let contents = lang::read_to_string(&node.get_file())?;
+
+        // cheaply check, with the span-free raw lexer, whether this file is
+        // nothing but whitespace/comments (a license-header-only stub, for
+        // instance) before paying for the full structured lex + parse,
+        // which would find zero entities/architectures in it anyway.
+        let is_code_free = lex_raw(&contents).iter().all(|t| {
+            matches!(
+                t.kind,
+                RawVhdlKind::Whitespace
+                    | RawVhdlKind::LineComment
+                    | RawVhdlKind::DelimComment { .. }
+            )
+        });
+        if is_code_free == true {
+            return Ok(());
+        }
+
+        // `VHDLParser::read` silently drops any token that failed to lex
+        // before handing the rest to the parser, so a malformed run of
+        // characters here would otherwise surface (if at all) as a
+        // confusing downstream parse error instead of pointing at the
+        // actual lexical mistake. Scan with the error-recovering tokenizer
+        // first and warn about anything it had to skip over, using the
+        // classified diagnostics so the warning names the specific mistake
+        // (an unterminated string, an unclosed comment, ...) instead of
+        // printing the raw lexer error text.
+        let (tokens, logger) = VhdlTokenizer::tokenize_with_diagnostics(&contents);
+        for log in logger.logs() {
+            println!(
+                "{}: {} {} in {}",
+                "warning".yellow(),
+                log.span.start,
+                log.message,
+                node.get_file(),
+            );
+        }
+
+        // a homoglyph identifier (one that mixes Unicode scripts, or reads
+        // identically to an unrelated ASCII identifier) is an easy way to
+        // silently pull in the wrong design unit, so flag it the same way
+        // a lex error is flagged above rather than only at code review time.
+        for finding in detect_confusables(&tokens) {
+            println!(
+                "{}: {} {} in {}",
+                "warning".yellow(),
+                finding.position,
+                finding.reason,
+                node.get_file(),
+            );
+        }
+
+        // orbit collects every design unit regardless of synthesis/simulation
+        // intent, so a `translate_off` region is still planned in; flag it so
+        // a user relying on a tool that *does* honor the pragma isn't
+        // surprised by a mismatch between what orbit and that tool each see.
+        for tk in &tokens {
+            if let Some(CommentDirective::TranslateOff) = tk.as_ref().as_directive() {
+                println!(
+                    "{}: {} synthesis translate_off region is not excluded by orbit in {}",
+                    "note".yellow(),
+                    tk.locate(),
+                    node.get_file(),
+                );
+            }
+        }
+
+        // a numeric or bit string literal that fails to evaluate (an
+        // AbstLiteral overflowing i128, a bit string literal with a digit
+        // that isn't legal under its base) lexes fine but denotes no real
+        // value, so flag it the same way the checks above flag a lexical
+        // or identifier problem rather than letting it surface only once
+        // something downstream tries to use the value.
+        for tk in &tokens {
+            let token = tk.as_ref();
+            let is_literal = matches!(
+                token,
+                VhdlToken::AbstLiteral(_) | VhdlToken::BitStrLiteral(_)
+            );
+            if is_literal == true {
+                if let Err(e) = token.eval() {
+                    println!(
+                        "{}: {} {} in {}",
+                        "warning".yellow(),
+                        tk.locate(),
+                        e,
+                        node.get_file(),
+                    );
+                }
+            }
+        }
+
         let symbols = match VHDLParser::read(&contents) {
             Ok(s) => s.into_symbols(),
             Err(e) => Err(ParseError::SourceCodeError(
@@ -941,6 +1243,226 @@ impl Plan {
         }
     }
 
+    /// Collects the identifier of every node in `graph` that is referenced
+    /// but not defined by any source file in the current dependency
+    /// closure (see [HdlNode::is_black_box]).
+    fn find_dangling_refs(graph: &GraphMap<CompoundIdentifier, HdlNode, ()>) -> Vec<CompoundIdentifier> {
+        graph
+            .get_map()
+            .iter()
+            .filter(|(_, node)| node.as_ref().is_black_box() == true)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Prints a diagnostic report of dangling references and, for each one,
+    /// the candidate ip(s) that would satisfy it if added as a dependency.
+    fn report_dangling_refs(suggestions: &Vec<DanglingRef>) {
+        for sug in suggestions {
+            match sug.get_candidates().as_slice() {
+                [] => println!(
+                    "{}: unresolved reference to \"{}\" and no installed ip defines it",
+                    "warning".yellow(),
+                    sug.get_identifier().get_suffix()
+                ),
+                candidates => {
+                    println!(
+                        "{}: unresolved reference to \"{}\"; add one of the following to {}:",
+                        "warning".yellow(),
+                        sug.get_identifier().get_suffix(),
+                        IP_MANIFEST_FILE
+                    );
+                    for c in candidates {
+                        println!("    {} = \"{}\"", c.get_name(), c.get_version());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Walks every black-box node in `graph` and, for each of its
+    /// referencing (successor) units, records one [UnresolvedRefFound].
+    ///
+    /// A single black box referenced by several units is reported once per
+    /// referencing unit, since each is an independent site that would need
+    /// fixing.
+    fn collect_unresolved_refs(
+        graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+    ) -> Vec<UnresolvedRefFound> {
+        let mut report = Vec::new();
+        for (key, node) in graph.get_map() {
+            if node.as_ref().is_black_box() == false {
+                continue;
+            }
+            // edges run from a dependency to the unit that instantiates it
+            for succ_index in graph.get_graph().successors(node.index()) {
+                let referencing_key = graph.get_key_by_index(succ_index).unwrap();
+                let referencing_node = graph.get_node_by_index(succ_index).unwrap();
+                let source_file = referencing_node
+                    .as_ref()
+                    .get_associated_files()
+                    .first()
+                    .map(|f| f.get_file().clone())
+                    .unwrap_or_default();
+                report.push(UnresolvedRefFound {
+                    unit: key.clone(),
+                    referenced_by: referencing_key.clone(),
+                    source_file: source_file,
+                    library: referencing_node.as_ref().get_library(),
+                });
+            }
+        }
+        report
+    }
+
+    /// Prints a diagnostic summary of every unresolved instantiation found
+    /// by [Self::collect_unresolved_refs].
+    fn report_unresolved_refs(unresolved: &Vec<UnresolvedRefFound>) {
+        for r in unresolved {
+            println!(
+                "{}: \"{}\" instantiated by \"{}\" in {} (library {}) resolved to an external/undefined unit",
+                "warning".yellow(),
+                r.unit.get_suffix(),
+                r.referenced_by.get_suffix(),
+                r.source_file,
+                r.library,
+            );
+        }
+    }
+
+    /// Narrows `file_order` down to the minimal set of files that must be
+    /// recompiled, given the on-disk fingerprints recorded by a prior plan.
+    ///
+    /// A file is dirty if its content hash or its direct dependency set
+    /// (`file_deps`) changed since the last recorded fingerprint; dirtiness
+    /// then propagates forward to every file that depends, even
+    /// transitively, on a dirty one. Since `file_order` is already
+    /// topologically sorted, a single forward pass that checks each file's
+    /// direct dependencies against the dirty set built up so far is enough
+    /// to catch the transitive case. A change in the selected top/bench
+    /// unit forces a full rebuild, since that affects which units are even
+    /// reachable.
+    fn compute_dirty_file_order<'a>(
+        file_order: Vec<&'a IpFileNode<'a>>,
+        file_deps: &HashMap<String, Vec<String>>,
+        cache: &mut ParseCache,
+        top_name: &str,
+        bench_name: &str,
+    ) -> Vec<&'a IpFileNode<'a>> {
+        if cache.selection_changed(top_name, bench_name) == true {
+            return file_order;
+        }
+
+        let mut dirty: HashSet<String> = HashSet::new();
+        for f in &file_order {
+            let file = f.get_file();
+            let deps = file_deps.get(file).cloned().unwrap_or_default();
+            let changed = cache.mark(file, deps.clone());
+            let depends_on_dirty = deps.iter().any(|d| dirty.contains(d));
+            if changed == true || depends_on_dirty == true {
+                dirty.insert(file.clone());
+            }
+        }
+
+        file_order
+            .into_iter()
+            .filter(|f| dirty.contains(f.get_file()))
+            .collect()
+    }
+
+    /// Finds `header` (e.g. `"[dependencies]"`) as a standalone table line
+    /// in `contents` and returns the byte offset just past it (including
+    /// its trailing newline), so a caller can insert new entries
+    /// immediately under that table.
+    ///
+    /// Unlike a raw substring search, a line is only a match when its
+    /// trimmed contents equal `header` exactly, so occurrences inside a
+    /// comment or string value (e.g. `# see [dependencies] docs`) are not
+    /// mistaken for the real table header.
+    fn find_table_header_end(contents: &str, header: &str) -> Option<usize> {
+        let mut offset = 0;
+        for line in contents.split_inclusive('\n') {
+            if line.trim_end_matches('\n').trim() == header {
+                return Some(offset + line.len());
+            }
+            offset += line.len();
+        }
+        None
+    }
+
+    /// Appends a dependency entry under the `[dependencies]` table of the
+    /// working ip's manifest for every dangling reference that has exactly
+    /// one candidate ip. References with zero or multiple candidates are
+    /// left for the user to resolve, since auto-adding would otherwise be
+    /// a guess.
+    fn auto_add_dependencies(working_ip: &Ip, suggestions: &Vec<DanglingRef>) -> Result<(), Fault> {
+        let man_path = working_ip.get_root().join(IP_MANIFEST_FILE);
+        let mut contents = fs::read_to_string(&man_path)?;
+
+        for sug in suggestions {
+            let candidate = match sug.get_candidates().as_slice() {
+                [single] => single,
+                _ => continue,
+            };
+            let entry = format!("{} = \"{}\"", candidate.get_name(), candidate.get_version());
+            // already satisfied (manually added or from a prior suggestion)
+            if contents.contains(&entry) {
+                continue;
+            }
+            match Self::find_table_header_end(&contents, "[dependencies]") {
+                Some(insert_at) => {
+                    contents.insert_str(insert_at, &format!("{}\n", entry));
+                }
+                // no `[dependencies]` table exists yet; append a new one
+                None => {
+                    contents.push_str(&format!("\n[dependencies]\n{}\n", entry));
+                }
+            }
+            println!(
+                "info: added {} = \"{}\" to {}",
+                candidate.get_name(),
+                candidate.get_version(),
+                IP_MANIFEST_FILE
+            );
+        }
+
+        fs::write(&man_path, contents)?;
+        Ok(())
+    }
+
+    /// Keeps only the files whose owning ip tags them under one of the
+    /// `active_scenarios`. An ip that defines no `[scenario]` groups of its
+    /// own opts all of its files into every scenario, so existing ip are
+    /// unaffected by this filter.
+    ///
+    /// Filtering happens before the files are parsed, so a file excluded
+    /// here neither creates a node nor contributes a black-box edge.
+    fn filter_by_scenario<'a>(
+        files: Vec<IpFileNode<'a>>,
+        active_scenarios: &HashSet<String>,
+    ) -> Vec<IpFileNode<'a>> {
+        let match_opts = glob::MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: false,
+            require_literal_leading_dot: false,
+        };
+        files
+            .into_iter()
+            .filter(|f| {
+                let groups = f.get_ip().get_man().get_scenarios();
+                if groups.is_empty() == true {
+                    return true;
+                }
+                groups.iter().any(|(name, patterns)| {
+                    active_scenarios.contains(name)
+                        && patterns
+                            .iter()
+                            .any(|p| p.inner().matches_with(f.get_file(), match_opts))
+                })
+            })
+            .collect()
+    }
+
     /// Builds a graph of design units. Used for planning
     pub fn build_full_graph<'a>(
         files: &'a Vec<IpFileNode>,
@@ -1063,6 +1585,11 @@ impl Plan {
     ) -> Result<(), Fault> {
         // only modify the lockfile if it is out-of-date
         if target.can_use_lock() == false || force == true {
+            // refuse to let the resolved dependency set change `Orbit.lock`
+            // when `--locked` (or `--frozen`) was specified
+            if crate::util::environment::is_locked() == true {
+                return Err(Box::new(Error::LockedOutOfDate(Hint::MakeLock)));
+            }
             // create build list
             let mut build_list: Vec<&Ip> = ip_graph
                 .get_map()
@@ -1415,10 +1942,14 @@ impl Plan {
     ///
     /// Several files may be associated with an index in the `global_graph`, so it is important
     /// to account for those too.
+    ///
+    /// Alongside the ordered file list, returns each file's direct dependency
+    /// file paths (sorted), keyed by file path; `Plan::compute_dirty_file_order`
+    /// reuses this to detect a dependency-edge change without recomputing it.
     fn determine_file_order<'a>(
         global_graph: &'a GraphMap<CompoundIdentifier, HdlNode, ()>,
         min_order: Vec<usize>,
-    ) -> Vec<&'a IpFileNode<'a>> {
+    ) -> (Vec<&'a IpFileNode<'a>>, HashMap<String, Vec<String>>) {
         // gather the files from each node in-order (multiple files can exist for a node)
         let mut file_map = HashMap::<String, (&IpFileNode, Vec<&HdlNode>)>::new();
         let mut file_order = Vec::<String>::new();
@@ -1474,12 +2005,29 @@ impl Plan {
             }
         }
         // topologically sort and transform into list of the file nodes
-        file_graph
-            .get_graph()
-            .topological_sort()
+        let sorted_indices = file_graph.get_graph().topological_sort();
+
+        // direct dependency file paths for each file, derived from the same
+        // `file_graph` edges used for the topological sort above
+        let mut file_deps: HashMap<String, Vec<String>> = HashMap::new();
+        for &i in &sorted_indices {
+            let file_node = *file_graph.get_key_by_index(i).unwrap();
+            let mut deps: Vec<String> = file_graph
+                .get_graph()
+                .predecessors(i)
+                .map(|p| file_graph.get_key_by_index(p).unwrap().get_file().clone())
+                .collect();
+            deps.sort();
+            deps.dedup();
+            file_deps.insert(file_node.get_file().clone(), deps);
+        }
+
+        let file_order = sorted_indices
             .into_iter()
             .map(|i| *file_graph.get_key_by_index(i).unwrap())
-            .collect()
+            .collect();
+
+        (file_order, file_deps)
     }
 
     /// Filters out the local nodes existing within the current IP from the `global_graph`.
@@ -1653,6 +2201,12 @@ impl Plan {
             &Scheme::default(),
             false,
             true,
+            false,
+            &None,
+            false,
+            false,
+            false,
+            false,
         );
         Ok(())
     }
@@ -1669,6 +2223,7 @@ pub enum PlanError {
     UnknownEntity(Identifier),
     Ambiguous(String, Vec<LangIdentifier>, Hint),
     Empty,
+    BlackBoxesFound(Vec<UnresolvedRefFound>),
 }
 
 impl std::error::Error for PlanError {}
@@ -1715,6 +2270,21 @@ impl std::fmt::Display for PlanError {
                 }),
                 hint,
             ),
+            Self::BlackBoxesFound(unresolved) => write!(
+                f,
+                "{} unresolved instantiation(s) found:\n{}",
+                unresolved.len(),
+                unresolved.iter().enumerate().fold(String::new(), |sum, (i, r)| {
+                    sum + &format!(
+                        "    \"{}\" instantiated by \"{}\" in {} (library {}){}",
+                        r.unit.get_suffix(),
+                        r.referenced_by.get_suffix(),
+                        r.source_file,
+                        r.library,
+                        if i + 1 < unresolved.len() { "\n" } else { "" }
+                    )
+                }),
+            ),
         }
     }
 }
@@ -1742,4 +2312,24 @@ mod test {
             vec![&9, &8, &7, &6, &5, &4]
         );
     }
+
+    #[test]
+    fn find_table_header_end_ignores_occurrences_outside_a_real_header_line() {
+        let contents = "\
+[ip]
+name = \"top\"
+# see [dependencies] docs for the expected format
+
+[dependencies]
+other = \"1.0.0\"
+";
+        let insert_at = Plan::find_table_header_end(contents, "[dependencies]").unwrap();
+        assert_eq!(&contents[insert_at..], "other = \"1.0.0\"\n");
+    }
+
+    #[test]
+    fn find_table_header_end_missing() {
+        let contents = "[ip]\nname = \"top\"\n";
+        assert_eq!(Plan::find_table_header_end(contents, "[dependencies]"), None);
+    }
 }
@@ -16,6 +16,7 @@
 //
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
 
@@ -300,7 +301,7 @@ impl Publish {
     pub fn check_graph_builds_okay(local_ip: &Ip, catalog: &Catalog) -> Result<(), Fault> {
         // use all language settings
         let lang = Language::default();
-        let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog, &lang)?;
+        let ip_graph = algo::compute_final_ip_graph(&local_ip, &catalog, &lang, &HashSet::new())?;
         let files = algo::build_ip_file_list(&ip_graph, &local_ip, &lang);
         let _global_graph = Plan::build_full_graph(&files)?;
         Ok(())
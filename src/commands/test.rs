@@ -23,6 +23,8 @@ use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
 use crate::core::ip::Ip;
+use crate::core::lang;
+use crate::core::lang::script::ScriptFormat;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::lang::Language;
 use crate::core::swap::StrSwapTable;
@@ -35,6 +37,8 @@ use crate::util::environment::ORBIT_OUTPUT_PATH;
 use crate::util::environment::ORBIT_TARGET;
 use crate::util::environment::{EnvVar, Environment, ORBIT_BLUEPRINT, ORBIT_TARGET_DIR};
 use crate::util::filesystem;
+use std::collections::HashSet;
+use std::fs;
 
 use super::plan::{self, Plan};
 
@@ -53,6 +57,14 @@ pub struct Test {
     command: Option<String>,
     filesets: Option<Vec<Fileset>>,
     bench: Option<Identifier>,
+    auto_dep: bool,
+    offline: bool,
+    scenario: Option<Vec<String>>,
+    no_default_scenario: bool,
+    strict: bool,
+    incr: bool,
+    minimal_versions: bool,
+    format: Option<ScriptFormat>,
 }
 
 impl Subcommand<Context> for Test {
@@ -65,14 +77,27 @@ impl Subcommand<Context> for Test {
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            auto_dep: cli.check(Arg::flag("auto-dep"))?,
+            offline: cli.check(Arg::flag("offline"))?,
+            no_default_scenario: cli.check(Arg::flag("no-default-scenario"))?,
+            strict: cli.check(Arg::flag("strict"))?,
+            incr: cli.check(Arg::flag("incr"))?,
+            minimal_versions: cli.check(Arg::flag("minimal-versions"))?,
             // Options
             dut: cli.get(Arg::option("dut").value("unit"))?,
+            // wontfix (here): `Arg::option("tb")` doubles as both the lookup
+            // key and the rendered `--tb` spelling; splitting those (plus
+            // the `<unit>` placeholder) into independent fields has to
+            // happen in `cliproc::Optional` itself. Blocked on an upstream
+            // `cliproc` change.
             bench: cli.get(Arg::option("tb").value("unit"))?,
             plan: cli.get(Arg::option("plan").value("format"))?,
             target: cli.get(Arg::option("target").value("name").switch('t'))?,
             target_dir: cli.get(Arg::option("target-dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            scenario: cli.get_all(Arg::option("scenario").value("name"))?,
+            format: cli.get(Arg::option("format").value("format"))?,
             // Remaining args
             args: cli.remainder()?,
         })
@@ -128,7 +153,7 @@ impl Subcommand<Context> for Test {
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
             .downloads(c.get_downloads_path())?;
-        let catalog = plan::resolve_missing_deps(c, &ip, catalog, self.force)?;
+        let catalog = plan::resolve_missing_deps(c, &ip, catalog, self.force, self.offline)?;
 
         self.run(
             &ip,
@@ -170,6 +195,12 @@ impl Test {
             &scheme,
             true,
             true,
+            self.auto_dep,
+            &self.scenario,
+            self.no_default_scenario,
+            self.strict,
+            self.incr,
+            self.minimal_versions,
         )?
         .unwrap_or_default();
 
@@ -178,6 +209,10 @@ impl Test {
             .join(target_dir)
             .join(&target.get_name());
 
+        if let Some(format) = &self.format {
+            self.write_script(working_ip, target, &output_path, format, mode)?;
+        }
+
         // prepare for build
         let envs = Environment::new()
             // read config.toml for setting any env variables
@@ -209,4 +244,27 @@ impl Test {
             Err(e) => Err(Error::TargetProcFailed(LastError(e.to_string())))?,
         }
     }
+
+    /// Writes a tool script in `format` alongside the blueprint, built from
+    /// every public unit in `working_ip` that applies to `target` (see
+    /// [lang::script::write_script]), for a `--command` that expects a
+    /// pre-built compile script rather than reading the blueprint itself.
+    fn write_script(
+        &self,
+        working_ip: &Ip,
+        target: &Target,
+        output_path: &std::path::Path,
+        format: &ScriptFormat,
+        lang: &Language,
+    ) -> Result<(), Fault> {
+        let units = working_ip.collect_units(self.force, lang, true)?;
+        let targets = HashSet::from([target.get_name().to_string()]);
+        let script = lang::script::write_script(&units, format, true, &targets)?;
+        let file_name = match format {
+            ScriptFormat::FileList => "compile.lst",
+            ScriptFormat::Modelsim => "compile.do",
+        };
+        fs::write(output_path.join(file_name), script)?;
+        Ok(())
+    }
 }
@@ -15,6 +15,8 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use std::collections::HashSet;
+
 use super::plan::PlanError;
 use crate::commands::helps::tree;
 use crate::commands::plan::Plan;
@@ -28,10 +30,13 @@ use crate::core::lang::node::HdlSymbol;
 use crate::core::lang::node::IdentifierFormat;
 use crate::core::lang::node::SubUnitNode;
 use crate::core::lang::reference::CompoundIdentifier;
+use crate::core::lang::sv::symbols::{into_dot_graph, SystemVerilogParser};
 use crate::core::lang::vhdl::token::Identifier as VhdlIdentifier;
 use crate::core::lang::Lang;
 use crate::core::lang::LangIdentifier;
+use crate::core::lang::parser::ParseError;
 use crate::core::lang::Language;
+use crate::core::lang::{self};
 use crate::error::Error;
 use crate::error::Hint;
 use crate::util::anyerror::Fault;
@@ -49,6 +54,8 @@ pub struct Tree {
     format: Option<IdentifierFormat>,
     ascii: bool,
     ip: bool,
+    json: bool,
+    dot: bool,
 }
 
 impl Subcommand<Context> for Tree {
@@ -59,6 +66,8 @@ impl Subcommand<Context> for Tree {
             // compress: cli.check(Arg::flag("compress"))?,
             ascii: cli.check(Arg::flag("ascii"))?,
             ip: cli.check(Arg::flag("ip"))?,
+            json: cli.check(Arg::flag("json"))?,
+            dot: cli.check(Arg::flag("dot"))?,
             format: cli.get(Arg::option("format").value("fmt"))?,
             roots: cli.get_all(Arg::positional("unit"))?,
         })
@@ -91,9 +100,16 @@ impl Tree {
         let working_lib = target.get_hdl_library();
 
         // build graph again but with entire set of all files available from all depdendencies
-        let ip_graph = algo::compute_final_ip_graph(&target, &catalog, mode)?;
+        let ip_graph = algo::compute_final_ip_graph(&target, &catalog, mode, &HashSet::new())?;
         let files = algo::build_ip_file_list(&ip_graph, &target, &mode);
 
+        // emit the SystemVerilog design-element graph as Graphviz DOT instead
+        // of a text/json tree
+        if self.dot == true {
+            println!("{}", Self::build_dot_graph(&files)?);
+            return Ok(());
+        }
+
         // build the complete graph (using entities as the nodes)
         let global_graph = Self::build_graph(&files)?;
 
@@ -147,6 +163,14 @@ impl Tree {
             }
         };
 
+        // emit the node/edge list as json instead of a text tree
+        if self.json == true {
+            return Self::print_json_graph(&global_graph, |n| {
+                n.display(self.format.as_ref().unwrap_or(&IdentifierFormat::Short))
+                    .to_string()
+            });
+        }
+
         // display each root's tree to the console
         roots
             .iter()
@@ -182,7 +206,14 @@ impl Tree {
 
     /// Construct and print the graph at an IP dependency level.
     fn run_ip_graph(&self, target: Ip, catalog: Catalog, mode: &Language) -> Result<(), Fault> {
-        let ip_graph = algo::compute_final_ip_graph(&target, &catalog, mode)?;
+        let ip_graph = algo::compute_final_ip_graph(&target, &catalog, mode, &HashSet::new())?;
+
+        // emit the node/edge list as json instead of a text tree
+        if self.json == true {
+            return Self::print_json_graph(&ip_graph, |n| {
+                n.as_ip().get_man().get_ip().into_ip_spec().to_string()
+            });
+        }
 
         let tree = ip_graph.get_graph().treeview(0);
 
@@ -207,6 +238,45 @@ impl Tree {
         Ok(())
     }
 
+    /// Schema version for the `--json` node/edge graph output.
+    ///
+    /// Bump this whenever the shape of the emitted object changes so editors
+    /// and CI consuming it can detect incompatible upgrades.
+    const JSON_SCHEMA_VERSION: u8 = 1;
+
+    /// Serializes any [GraphMap] to an explicit node/edge list and prints it
+    /// as a single line of JSON, mirroring the way `cargo metadata` exposes
+    /// its dependency graph.
+    fn print_json_graph<K, V, E>(
+        graph: &GraphMap<K, V, E>,
+        label: impl Fn(&V) -> String,
+    ) -> Result<(), Fault>
+    where
+        K: std::hash::Hash + Eq + Clone,
+    {
+        let nodes: Vec<serde_json::Value> = graph
+            .iter()
+            .map(|(_, v, _)| serde_json::json!({ "id": label(v) }))
+            .collect();
+
+        let mut edges: Vec<serde_json::Value> = Vec::new();
+        for (key, _, mut successors) in graph.iter() {
+            let from = label(graph.get_node_by_key(key).unwrap().as_ref());
+            while let Some((n_key, _, _)) = successors.next() {
+                let to = label(graph.get_node_by_key(n_key).unwrap().as_ref());
+                edges.push(serde_json::json!({ "from": from, "to": to }));
+            }
+        }
+
+        let doc = serde_json::json!({
+            "version": Self::JSON_SCHEMA_VERSION,
+            "nodes": nodes,
+            "edges": edges,
+        });
+        println!("{}", serde_json::to_string(&doc)?);
+        Ok(())
+    }
+
     /// Converts the original treeview text from using extended ascii characters
     /// to orginal ascii characters.
     fn to_ascii(s: &str) -> String {
@@ -224,6 +294,27 @@ impl Tree {
         transform
     }
 
+    /// Renders the SystemVerilog design elements across `files` as a single
+    /// Graphviz DOT digraph (see [into_dot_graph]).
+    ///
+    /// Non-SystemVerilog files are skipped; `--dot` is currently scoped to
+    /// the SystemVerilog design-element graph rather than the mixed-language
+    /// entity tree the rest of this command prints.
+    fn build_dot_graph(files: &Vec<IpFileNode>) -> Result<String, Fault> {
+        let mut symbols = Vec::new();
+        for file in files.iter().filter(|f| f.get_language() == &Lang::SystemVerilog) {
+            let contents = lang::read_to_string(file.get_file())?;
+            match SystemVerilogParser::read(&contents) {
+                Ok(s) => symbols.extend(s.into_symbols()),
+                Err(e) => Err(ParseError::SourceCodeError(
+                    file.get_file().clone(),
+                    e.render(&contents, file.get_file()),
+                ))?,
+            }
+        }
+        Ok(into_dot_graph(&symbols))
+    }
+
     /// Constructs a graph of the design heirarchy with entity nodes.
     fn build_graph<'a>(
         files: &'a Vec<IpFileNode>,
@@ -0,0 +1,65 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::path::PathBuf;
+
+use crate::commands::helps::unpack;
+use crate::core::context::Context;
+use crate::util::anyerror::AnyError;
+use crate::util::archive;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Unpack {
+    archive: PathBuf,
+    output: Option<PathBuf>,
+}
+
+impl Subcommand<Context> for Unpack {
+    fn interpret(cli: &mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(unpack::HELP))?;
+        Ok(Unpack {
+            output: cli.get(Arg::option("output").value("dir"))?,
+            archive: cli.require(Arg::positional("archive"))?,
+        })
+    }
+
+    fn execute(self, _c: &Context) -> proc::Result {
+        if self.archive.exists() == false {
+            return Err(AnyError(format!("archive {:?} does not exist", self.archive)))?;
+        }
+
+        let dest = self.output.unwrap_or_else(|| {
+            PathBuf::from(self.archive.file_stem().unwrap_or_default())
+        });
+
+        if dest.exists() == true {
+            return Err(AnyError(format!(
+                "destination directory {:?} already exists",
+                dest
+            )))?;
+        }
+
+        archive::unpack(&self.archive, &dest)?;
+
+        println!("info: unpacked archive into {:?}", dest);
+
+        Ok(())
+    }
+}
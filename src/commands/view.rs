@@ -5,6 +5,7 @@ use crate::core::ip::{Ip, PartialIpSpec};
 use crate::core::lang::LangUnit;
 use crate::core::pubfile::Visibility;
 use crate::core::version;
+use crate::core::version::VersionRange;
 use crate::error::{Error, Hint};
 use crate::util::anyerror::AnyError;
 use crate::util::anyerror::Fault;
@@ -14,12 +15,31 @@ use std::env::current_dir;
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
 
+/// Output mode for `orbit view`, selected with `--format <fmt>`.
+#[derive(Debug, PartialEq)]
+enum ViewFormat {
+    Json,
+}
+
+impl std::str::FromStr for ViewFormat {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            _ => Err(AnyError(format!("format can only be 'json'"))),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct View {
     tags: bool,
     units: bool,
     ip: Option<PartialIpSpec>,
     all: bool,
+    format: Option<ViewFormat>,
+    range: Option<VersionRange>,
 }
 
 impl Subcommand<Context> for View {
@@ -29,15 +49,19 @@ impl Subcommand<Context> for View {
             all: cli.check(Arg::flag("all").switch('a'))?,
             tags: cli.check(Arg::flag("versions").switch('v'))?,
             units: cli.check(Arg::flag("units").switch('u'))?,
+            format: cli.get(Arg::option("format").value("fmt"))?,
+            range: cli.get(Arg::option("range").value("version:version"))?,
             ip: cli.get(Arg::positional("ip"))?,
         })
     }
 
     fn execute(self, c: &Context) -> proc::Result {
-        // collect all manifests available (load catalog)
+        // collect all manifests available (load catalog, including ip known
+        // only to a configured channel so an uninstalled ip can still be viewed)
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
-            .downloads(c.get_downloads_path())?;
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
 
         let dev_ip: Option<Result<Ip, Fault>> = {
             match Context::find_ip_path(&current_dir().unwrap()) {
@@ -59,7 +83,13 @@ impl Subcommand<Context> for View {
                     if let Some(slot) = lvl.get_download(spec.get_version()) {
                         slot
                     } else {
-                        return Err(Error::IpNotFoundInCache(spec.to_string()))?;
+                        // fall back to a channel's registry listing, so an ip
+                        // that was never installed/downloaded can still be viewed
+                        if let Some(slot) = lvl.get_available(spec.get_version()) {
+                            slot
+                        } else {
+                            return Err(Error::IpNotFoundInCache(spec.to_string()))?;
+                        }
                     }
                 }
             } else {
@@ -95,13 +125,13 @@ impl Subcommand<Context> for View {
                     false,
                     ip.into_public_list(),
                 )?;
-                println!(
-                    "{}",
-                    Self::format_units_table(
-                        units.into_iter().map(|(_, unit)| unit).collect(),
-                        self.all
-                    )
-                );
+                let units: Vec<LangUnit> = units.into_iter().map(|(_, unit)| unit).collect();
+                match self.format {
+                    Some(ViewFormat::Json) => {
+                        println!("{}", Self::format_units_json(units, self.all)?)
+                    }
+                    None => println!("{}", Self::format_units_table(units, self.all)),
+                }
             } else {
                 // a 'virtual' ip, so try to extract units from
                 println!(
@@ -116,38 +146,50 @@ impl Subcommand<Context> for View {
         // display all installed versions in the cache
         if self.tags == true {
             let specified_ver = self.ip.as_ref().unwrap().get_version().as_specific();
+            let range = self.range.as_ref();
 
             return match catalog.get_possible_versions(ip.get_man().get_ip().get_name()) {
                 Some(vers) => {
-                    match vers.len() {
-                        0 => {
-                            println!("info: no versions in the cache")
-                        }
-                        _ => {
-                            let mut data = String::new();
-                            // let header = format!(
-                            //     "{:<10}{:<11}\n{2:->10}{2:->11}\n",
-                            //     "Version", "Status", " ",
-                            // );
-                            // data.push_str(&header);
-                            // further restrict versions if a particular version is set
-                            vers.iter()
-                                .filter(move |p| {
-                                    specified_ver.is_none()
-                                        || version::is_compatible(
-                                            specified_ver.unwrap(),
-                                            &p.get_version(),
-                                        ) == true
+                    let vers: Vec<_> = vers
+                        .into_iter()
+                        .filter(move |p| match range {
+                            Some(range) => range.in_range(p.get_version()),
+                            None => {
+                                specified_ver.is_none()
+                                    || version::is_compatible(
+                                        specified_ver.unwrap(),
+                                        &p.get_version(),
+                                    ) == true
+                            }
+                        })
+                        .collect();
+                    match self.format {
+                        Some(ViewFormat::Json) => {
+                            let entries: Vec<serde_json::Value> = vers
+                                .iter()
+                                .map(|v| {
+                                    serde_json::json!({
+                                        "version": v.get_version().to_string(),
+                                        "state": v.get_state().to_string(),
+                                    })
                                 })
-                                .for_each(|v| {
+                                .collect();
+                            println!("{}", serde_json::to_string(&entries)?);
+                        }
+                        None => match vers.len() {
+                            0 => println!("info: no versions in the cache"),
+                            _ => {
+                                let mut data = String::new();
+                                vers.iter().for_each(|v| {
                                     data.push_str(&format!(
                                         "{:<10}{:<11}\n",
                                         v.get_version().to_string(),
                                         v.get_state().to_string()
                                     ));
                                 });
-                            println!("{}", data);
-                        }
+                                println!("{}", data);
+                            }
+                        },
                     }
                     Ok(())
                 }
@@ -155,9 +197,11 @@ impl Subcommand<Context> for View {
             };
         }
 
-        // print the manifest data "pretty"
-        let s = toml::to_string_pretty(ip.get_man())?;
-        println!("{}", s);
+        // print the manifest data
+        match self.format {
+            Some(ViewFormat::Json) => println!("{}", serde_json::to_string(ip.get_man())?),
+            None => println!("{}", toml::to_string_pretty(ip.get_man())?),
+        }
         Ok(())
     }
 }
@@ -197,10 +241,35 @@ impl View {
 
         result
     }
+
+    /// Serializes the primary design units as a single line of JSON, suitable
+    /// for scripting against instead of screen-scraping [Self::format_units_table].
+    fn format_units_json(table: Vec<LangUnit>, all: bool) -> Result<String, Fault> {
+        let mut table = table;
+
+        table.sort_by(|a, b| match a.get_visibility().cmp(&b.get_visibility()) {
+            Ordering::Equal => a.get_name().cmp(&b.get_name()),
+            Ordering::Less => Ordering::Less,
+            Ordering::Greater => Ordering::Greater,
+        });
+
+        let entries: Vec<serde_json::Value> = table
+            .iter()
+            .filter(|unit| all == true || unit.get_visibility() == &Visibility::Public)
+            .map(|unit| {
+                serde_json::json!({
+                    "name": unit.get_name().to_string(),
+                    "type": unit.to_string(),
+                    "visibility": unit.get_visibility().to_string(),
+                })
+            })
+            .collect();
+
+        Ok(serde_json::to_string(&entries)?)
+    }
 }
 
 // FUTURE FLAGS
 // ============
 // --changes                   view the changelog
 // --readme                    view the readme
-// --range <version:version>   narrow the displayed version list
@@ -16,10 +16,12 @@
 //
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::util::anyerror::{AnyError, CodeFault, Fault};
 use crate::util::graphmap::GraphMap;
+use colored::Colorize;
 use std::hash::Hash;
 use tempfile::tempdir;
 
@@ -37,7 +39,9 @@ use super::fileset;
 use super::ip::PartialIpSpec;
 use super::lang::sv::token::tokenizer::SystemVerilogTokenizer;
 use super::lang::verilog::token::tokenizer::VerilogTokenizer;
+use super::lang::cross;
 use super::lang::{sv, verilog, vhdl, Lang, LangIdentifier};
+use crate::core::lang::reference::CompoundIdentifier;
 use crate::core::lang::Language;
 
 /// Constructs an ip-graph from a lockfile.
@@ -68,6 +72,7 @@ fn graph_ip<'a>(
     root: &'a Ip,
     catalog: &'a Catalog<'a>,
     mode: &Language,
+    targets: &HashSet<String>,
 ) -> Result<GraphMap<IpSpec, IpNode<'a>, ()>, CodeFault> {
     // create empty graph
     let mut g = GraphMap::new();
@@ -79,7 +84,7 @@ fn graph_ip<'a>(
     let mut processing = vec![(t, root)];
 
     // add root's identifiers and parse files according to the correct language settings
-    let mut unit_map = root.collect_units(true, mode, false)?;
+    let mut unit_map = root.collect_units_for_targets(true, mode, false, targets)?;
 
     let mut is_root: bool = true;
 
@@ -101,7 +106,7 @@ fn graph_ip<'a>(
                                 existing_node.index()
                             } else {
                                 // check if identifiers are already taken in graph
-                                let units = relative_ip.collect_units(false, mode, true)?;
+                                let units = relative_ip.collect_units_for_targets(false, mode, true, targets)?;
                                 if let Some(dupe) =
                                     units.iter().find(|(key, _)| unit_map.contains_key(key))
                                 {
@@ -176,7 +181,7 @@ fn graph_ip<'a>(
                                         existing_node.index()
                                     } else {
                                         // check if identifiers are already taken in graph
-                                        let units = cached_ip.collect_units(false, mode, true)?;
+                                        let units = cached_ip.collect_units_for_targets(false, mode, true, targets)?;
                                         let dst = if let Some(dupe) =
                                             units.iter().find(|(key, _)| unit_map.contains_key(key))
                                         {
@@ -265,9 +270,10 @@ pub fn compute_final_ip_graph<'a>(
     target: &'a Ip,
     catalog: &'a Catalog<'a>,
     mode: &Language,
+    build_targets: &HashSet<String>,
 ) -> Result<GraphMap<IpSpec, IpNode<'a>, ()>, CodeFault> {
     // collect rough outline of ip graph (after this function, the correct files according to language are kept)
-    let mut rough_ip_graph = graph_ip(&target, &catalog, mode)?;
+    let mut rough_ip_graph = graph_ip(&target, &catalog, mode, build_targets)?;
 
     // keep track of list of neighbors that must perform dst and their lookup-tables to use after processing all direct impacts
     let mut transforms = HashMap::<IpSpec, HashMap<LangIdentifier, String>>::new();
@@ -321,6 +327,15 @@ pub fn compute_final_ip_graph<'a>(
     }
     // println!("{:?}", transforms);
 
+    // for every identifier the loop above decided needs a defensive rename,
+    // independently double-check with `cross::resolve`'s layered precedence
+    // (the ip's own units, then its direct dependencies, then anything only
+    // reachable transitively) whether a real build would have actually been
+    // ambiguous, or whether precedence alone would have picked a unit
+    // unambiguously; either way this is purely informational; DST still
+    // renames defensively regardless of what `cross::resolve` reports.
+    warn_about_cross_resolution(target, &rough_ip_graph, mode, &transforms);
+
     // perform each dynamic symbol transform
     let mut transforms_iter = transforms.into_iter();
     while let Some((key, lut)) = transforms_iter.next() {
@@ -335,6 +350,94 @@ pub fn compute_final_ip_graph<'a>(
     Ok(rough_ip_graph)
 }
 
+/// Reports, for every identifier `transforms` marked as needing a defensive
+/// rename, what [cross::resolve] finds when it searches `target`'s own
+/// units, then its direct dependencies, then everything only reachable
+/// transitively. Read-only: nothing here feeds back into `transforms` or
+/// `rough_ip_graph`, so a wrong or incomplete answer here cannot break a
+/// build, only misreport a diagnostic.
+///
+/// Only the immediate dependency layer is modeled as `direct`; everything
+/// else already present in `rough_ip_graph` is treated as `transitive`,
+/// which is coarser than true transitive depth but sufficient to tell
+/// "found unambiguously nearby" from "actually contested."
+fn warn_about_cross_resolution(
+    target: &Ip,
+    rough_ip_graph: &GraphMap<IpSpec, IpNode, ()>,
+    mode: &Language,
+    transforms: &HashMap<IpSpec, HashMap<LangIdentifier, String>>,
+) -> () {
+    let root_key = target.get_man().get_ip().into_ip_spec();
+    let root_index = match rough_ip_graph.get_node_by_key(&root_key) {
+        Some(n) => n.index(),
+        None => return,
+    };
+
+    let own_units = match target.collect_units(true, mode, target.has_public_list()) {
+        Ok(units) => units,
+        Err(_) => return,
+    };
+
+    let mut direct_keys: HashSet<IpSpec> = HashSet::new();
+    let mut direct: cross::DependencyScope = Vec::new();
+    // edges run from a dependency to the ip that requires it
+    let mut dependencies = rough_ip_graph.get_graph().predecessors(root_index);
+    while let Some(i) = dependencies.next() {
+        let key = match rough_ip_graph.get_key_by_index(i) {
+            Some(k) => k,
+            None => continue,
+        };
+        direct_keys.insert(key.clone());
+        let dep_ip = rough_ip_graph.get_node_by_key(key).unwrap().as_ref().as_ip();
+        if let Ok(units) = dep_ip.collect_units(true, mode, dep_ip.has_public_list()) {
+            direct.push((key.get_name().clone(), units));
+        }
+    }
+
+    let mut transitive: cross::DependencyScope = Vec::new();
+    for (key, node) in rough_ip_graph.get_map().iter() {
+        if key == &root_key || direct_keys.contains(key) {
+            continue;
+        }
+        let dep_ip = node.as_ref().as_ip();
+        if let Ok(units) = dep_ip.collect_units(true, mode, dep_ip.has_public_list()) {
+            transitive.push((key.get_name().clone(), units));
+        }
+    }
+
+    let mut checked: HashSet<LangIdentifier> = HashSet::new();
+    for lut in transforms.values() {
+        for name in lut.keys() {
+            if checked.insert(name.clone()) == false {
+                continue;
+            }
+            let id = cross::CrossIdentifier::new(mode.clone(), name.clone());
+            match cross::resolve(&id, &own_units, &direct, &transitive) {
+                Ok((cross::Scope::CurrentIp, _)) | Ok((cross::Scope::DirectDependency(_), _)) => {
+                    println!(
+                        "{}: '{}' would resolve unambiguously under precedence, but orbit still renames it defensively",
+                        "note".yellow(),
+                        name,
+                    );
+                }
+                Ok((cross::Scope::TransitiveDependency(_), _)) | Err(cross::CrossResolveError::NotFound(_)) => (),
+                Err(cross::CrossResolveError::Ambiguous { name, competing }) => {
+                    println!(
+                        "{}: '{}' is genuinely ambiguous between {}; orbit's defensive rename avoids the collision",
+                        "warning".yellow(),
+                        name,
+                        competing
+                            .iter()
+                            .map(|p| format!("'{}'", p))
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                    );
+                }
+            }
+        }
+    }
+}
+
 /// Take the ip graph and create the entire space of HDL files that could be used for the current design.
 pub fn build_ip_file_list<'a>(
     ip_graph: &'a GraphMap<IpSpec, IpNode<'a>, ()>,
@@ -345,7 +448,12 @@ pub fn build_ip_file_list<'a>(
     ip_graph.get_map().iter().for_each(|(_, ip)| {
         let inner_ip = ip.as_ref().as_ip();
         let non_private_list = inner_ip.into_non_private_list();
-        crate::util::filesystem::gather_current_files(&inner_ip.get_root(), false)
+        crate::util::filesystem::gather_current_files(
+            &inner_ip.get_root(),
+            false,
+            crate::util::filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        )
             .into_iter()
             .filter(|f| {
                 working_ip == inner_ip
@@ -448,8 +556,10 @@ impl<'a> IpNode<'a> {
         crate::util::filesystem::copy(
             &self.original.get_root(),
             &temp_path,
-            true,
+            Some(crate::util::filetype::MINIMAL_TYPES),
             Some(self.original.get_files_to_keep()),
+            crate::util::filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
         )
         .unwrap();
 
@@ -457,7 +567,12 @@ impl<'a> IpNode<'a> {
         let temp_ip = Ip::load(temp_path, false).unwrap();
 
         // edit all vhdl files
-        let files = crate::util::filesystem::gather_current_files(temp_ip.get_root(), false);
+        let files = crate::util::filesystem::gather_current_files(
+            temp_ip.get_root(),
+            false,
+            crate::util::filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        );
         for file in &files {
             // perform dst on the data (VHDL)
             if fileset::is_vhdl(&file) == true {
@@ -522,8 +637,10 @@ fn install_dst(source_ip: &Ip, root: &PathBuf, mapping: &HashMap<LangIdentifier,
     crate::util::filesystem::copy(
         &source_ip.get_root(),
         &cache_path,
-        true,
+        Some(crate::util::filetype::MINIMAL_TYPES),
         Some(source_ip.get_files_to_keep()),
+        crate::util::filesystem::default_thread_count(),
+        &std::collections::HashMap::new(),
     )
     .unwrap();
     let cached_ip = Ip::load(cache_path, false).unwrap();
@@ -595,3 +712,64 @@ impl<'a> IpFileNode<'a> {
         self.ip.get_hdl_library()
     }
 }
+
+/// An HDL reference (component/entity/module instantiation) left dangling by
+/// [crate::commands::plan::Plan::build_full_graph] — nothing in the current
+/// dependency closure defines it — paired with the installed ip(s) that
+/// would satisfy it if declared as a dependency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DanglingRef {
+    identifier: CompoundIdentifier,
+    candidates: Vec<IpSpec>,
+}
+
+impl DanglingRef {
+    pub fn get_identifier(&self) -> &CompoundIdentifier {
+        &self.identifier
+    }
+
+    pub fn get_candidates(&self) -> &Vec<IpSpec> {
+        &self.candidates
+    }
+}
+
+/// Matches every dangling reference in `refs` against the primary design
+/// units of each installed ip in `catalog`.
+///
+/// A reference with a library prefix only matches an ip whose own hdl
+/// library carries that same name; an unprefixed reference (the common
+/// case for component/entity instantiation) matches on unit name alone
+/// across every installed ip. Only installed ip are searched, since a
+/// downloaded-but-not-installed or available-but-not-downloaded ip has no
+/// guaranteed-extracted source to collect units from.
+pub fn suggest_dependencies(
+    refs: &Vec<CompoundIdentifier>,
+    catalog: &Catalog,
+    mode: &Language,
+) -> Vec<DanglingRef> {
+    refs.iter()
+        .map(|iden| {
+            let mut candidates = Vec::new();
+            for level in catalog.inner().values() {
+                for ip in level.get_installations() {
+                    if let Some(lib) = iden.get_prefix() {
+                        if lib != &ip.get_hdl_library() {
+                            continue;
+                        }
+                    }
+                    let units = match ip.collect_units(false, mode, true) {
+                        Ok(u) => u,
+                        Err(_) => continue,
+                    };
+                    if units.contains_key(iden.get_suffix()) {
+                        candidates.push(ip.get_man().get_ip().into_ip_spec());
+                    }
+                }
+            }
+            DanglingRef {
+                identifier: iden.clone(),
+                candidates,
+            }
+        })
+        .collect()
+}
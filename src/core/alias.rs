@@ -0,0 +1,164 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! User-defined command aliases, configured under `[alias]` in `config.toml`
+//! (e.g. `alias.b = "build --release"`) and resolved against the raw
+//! argument stream before a subcommand is dispatched, mirroring cargo's
+//! `aliased_command`.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_derive::{Deserialize, Serialize};
+
+/// The value of a single `alias.<name>` entry: a single string that is
+/// split on whitespace, or an explicit list of already-split tokens.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AliasValue {
+    Single(String),
+    List(Vec<String>),
+}
+
+impl AliasValue {
+    /// Splits this alias into its expanded sequence of tokens.
+    fn expand(&self) -> Vec<String> {
+        match self {
+            Self::Single(s) => s.split_whitespace().map(String::from).collect(),
+            Self::List(l) => l.clone(),
+        }
+    }
+}
+
+pub type Aliases = HashMap<String, AliasValue>;
+
+/// Finds the index of the first positional (non-flag) argument in `args`,
+/// skipping over `program` (`args[0]`) and any global flag/option that
+/// appears before it.
+///
+/// `options_with_value` lists the global option names (e.g. `"--color"`)
+/// whose following token must also be skipped, rather than mistaken for
+/// the subcommand.
+pub fn find_subcommand_index(args: &[String], options_with_value: &[&str]) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg.starts_with("--") {
+            i += if options_with_value.contains(&arg.as_str()) {
+                2
+            } else {
+                1
+            };
+            continue;
+        }
+        return Some(i);
+    }
+    None
+}
+
+/// Resolves a user-defined alias found at `args[index]`, splicing its
+/// expansion back into `args` in place.
+///
+/// A name in `known` (a built-in subcommand) always shadows an alias of
+/// the same name. Self-referential/recursive aliases are caught with a
+/// visited set and left as the last-expanded token rather than looping
+/// forever.
+pub fn resolve(args: &mut Vec<String>, index: usize, aliases: &Aliases, known: &[&str]) {
+    let mut visited: HashSet<String> = HashSet::new();
+    loop {
+        let token = match args.get(index) {
+            Some(t) => t.clone(),
+            None => return,
+        };
+        // a real subcommand always shadows an alias of the same name
+        if known.contains(&token.as_str()) {
+            return;
+        }
+        // stop rather than loop forever on a self-referential alias chain
+        if visited.contains(&token) {
+            return;
+        }
+        let expansion = match aliases.get(&token) {
+            Some(v) => v.expand(),
+            None => return,
+        };
+        visited.insert(token);
+        args.splice(index..index + 1, expansion);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expands_single_string_alias() {
+        let mut aliases = Aliases::new();
+        aliases.insert(
+            "b".to_string(),
+            AliasValue::Single("build --release".to_string()),
+        );
+        let mut args = vec!["orbit".to_string(), "b".to_string()];
+        resolve(&mut args, 1, &aliases, &["build", "plan"]);
+        assert_eq!(args, vec!["orbit", "build", "--release"]);
+    }
+
+    #[test]
+    fn expands_list_alias() {
+        let mut aliases = Aliases::new();
+        aliases.insert(
+            "b".to_string(),
+            AliasValue::List(vec!["build".to_string(), "--release".to_string()]),
+        );
+        let mut args = vec!["orbit".to_string(), "b".to_string()];
+        resolve(&mut args, 1, &aliases, &["build", "plan"]);
+        assert_eq!(args, vec!["orbit", "build", "--release"]);
+    }
+
+    #[test]
+    fn builtin_subcommand_shadows_alias() {
+        let mut aliases = Aliases::new();
+        aliases.insert("build".to_string(), AliasValue::Single("plan".to_string()));
+        let mut args = vec!["orbit".to_string(), "build".to_string()];
+        resolve(&mut args, 1, &aliases, &["build", "plan"]);
+        assert_eq!(args, vec!["orbit", "build"]);
+    }
+
+    #[test]
+    fn stops_on_recursive_alias() {
+        let mut aliases = Aliases::new();
+        aliases.insert("a".to_string(), AliasValue::Single("b".to_string()));
+        aliases.insert("b".to_string(), AliasValue::Single("a".to_string()));
+        let mut args = vec!["orbit".to_string(), "a".to_string()];
+        resolve(&mut args, 1, &aliases, &["build"]);
+        assert_eq!(args, vec!["orbit", "a"]);
+    }
+
+    #[test]
+    fn finds_subcommand_after_global_flags() {
+        let args: Vec<String> = vec!["orbit", "--color", "always", "--force", "build"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(find_subcommand_index(&args, &["--color"]), Some(4));
+    }
+
+    #[test]
+    fn no_subcommand_given() {
+        let args: Vec<String> = vec!["orbit".to_string(), "--version".to_string()];
+        assert_eq!(find_subcommand_index(&args, &["--color"]), None);
+    }
+}
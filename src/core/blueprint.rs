@@ -1,6 +1,8 @@
 use crate::core::fileset;
+use crate::core::lang::Lang;
 use crate::util::anyerror::AnyError;
 use cliproc::cli::Error;
+use serde_json::json;
 use std::io::Write;
 use std::{fs::File, path::PathBuf, str::FromStr};
 
@@ -10,6 +12,14 @@ use super::algo::IpFileNode;
 pub enum Scheme {
     Tsv,
     Json,
+    /// A plain `.f` file listing one resolved file path per line.
+    Flist,
+    /// A Questa/ModelSim `.do` script with `vcom`/`vlog` lines.
+    Vsim,
+    /// A Synopsys VCS `.f` file list.
+    Vcs,
+    /// A Vivado `.tcl` script with `read_vhdl`/`read_verilog` lines.
+    Vivado,
 }
 
 impl Default for Scheme {
@@ -25,6 +35,10 @@ impl FromStr for Scheme {
         match s.to_ascii_lowercase().as_ref() {
             "tsv" => Ok(Self::Tsv),
             "json" => Ok(Self::Json),
+            "flist" => Ok(Self::Flist),
+            "vsim" => Ok(Self::Vsim),
+            "vcs" => Ok(Self::Vcs),
+            "vivado" => Ok(Self::Vivado),
             _ => Err(AnyError(format!("unknown file format: {}", s))),
         }
     }
@@ -60,9 +74,63 @@ impl<'a, 'b> Instruction<'a, 'b> {
                 }
                 Self::Auxiliary(key, lib, file) => format!("{}\t{}\t{}", key, lib, file),
             },
-            Scheme::Json => {
-                todo!()
-            }
+            // one compact json object per line, mirroring the `Tsv` scheme's
+            // one-record-per-line layout so the rest of `Blueprint::write`
+            // (which joins `Instruction::write` outputs with newlines) needs
+            // no special-casing for this scheme
+            Scheme::Json => match &self {
+                Self::Hdl(node) => {
+                    let fileset = if fileset::is_verilog(node.get_file()) == true {
+                        "VLOG"
+                    } else if fileset::is_vhdl(node.get_file()) == true {
+                        "VHDL"
+                    } else if fileset::is_systemverilog(node.get_file()) == true {
+                        "SYSV"
+                    } else {
+                        panic!("unknown file in source file set")
+                    };
+                    json!({
+                        "fileset": fileset,
+                        "library": node.get_ip().get_man().get_hdl_library().to_string(),
+                        "file": node.get_file(),
+                    })
+                    .to_string()
+                }
+                Self::Auxiliary(key, lib, file) => json!({
+                    "fileset": key,
+                    "library": lib,
+                    "file": file,
+                })
+                .to_string(),
+            },
+            Scheme::Flist | Scheme::Vcs => match &self {
+                Self::Hdl(node) => node.get_file().to_string(),
+                Self::Auxiliary(_, _, file) => file.to_string(),
+            },
+            Scheme::Vsim => match &self {
+                Self::Hdl(node) => {
+                    let cmd = match node.get_language() {
+                        Lang::Vhdl => "vcom",
+                        Lang::Verilog | Lang::SystemVerilog => "vlog",
+                    };
+                    format!("{} -work {} {}", cmd, node.get_library(), node.get_file())
+                }
+                Self::Auxiliary(key, _, file) => format!("# {}: {}", key, file),
+            },
+            Scheme::Vivado => match &self {
+                Self::Hdl(node) => match node.get_language() {
+                    Lang::Vhdl => {
+                        format!(
+                            "read_vhdl -library {} {{{}}}",
+                            node.get_library(),
+                            node.get_file()
+                        )
+                    }
+                    Lang::Verilog => format!("read_verilog {{{}}}", node.get_file()),
+                    Lang::SystemVerilog => format!("read_verilog -sv {{{}}}", node.get_file()),
+                },
+                Self::Auxiliary(key, _, file) => format!("# {}: {}", key, file),
+            },
         }
     }
 }
@@ -94,6 +162,10 @@ impl<'a, 'b> Blueprint<'a, 'b> {
         String::from(match self.scheme {
             Scheme::Tsv => "blueprint.tsv",
             Scheme::Json => "blueprint.json",
+            Scheme::Flist => "blueprint.f",
+            Scheme::Vsim => "blueprint.do",
+            Scheme::Vcs => "blueprint.vcs.f",
+            Scheme::Vivado => "blueprint.tcl",
         })
     }
 
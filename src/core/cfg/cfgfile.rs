@@ -3,6 +3,7 @@
 //!     A `cfgfile` is the main file format used to store data for Orbit. It
 //!     resembles a ini-like syntax and structure composed of "tables" 
 //!     (sections) and "fields" (key-value pairs).
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use crate::core::cfg::field;
 use std::str::FromStr;
@@ -12,7 +13,7 @@ type Col = usize;
 #[derive(Debug, PartialEq, Clone)]
 struct Pos(Line, Col);
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 enum TokenType {
     COMMENT(String),    // ; or #
     ASSIGNMENT,         // =
@@ -97,72 +98,353 @@ enum CfgState {
     NORMAL,
 }
 
-struct CfgLanguage {
-    map: HashMap::<field::Identifier, field::Value>,
+/// A node in a [CfgTrie], addressed by one dot-separated segment of a
+/// [field::Identifier].
+#[derive(Debug, Default)]
+struct TrieNode {
+    value: Option<field::Value>,
+    children: BTreeMap<String, TrieNode>,
 }
 
-impl CfgLanguage {
+/// Stores cfg fields in a trie keyed on the dot-separated segments of their
+/// identifier, rather than a flat map keyed on the fully-prepended name. This
+/// allows enumerating "all keys under `[core]`" or walking a table's fields
+/// in order, which a flat map cannot do without re-splitting every key.
+#[derive(Debug, Default)]
+struct CfgTrie {
+    root: TrieNode,
+}
+
+impl CfgTrie {
     fn new() -> Self {
-        CfgLanguage { 
-            map: HashMap::new(),
-            // for saving, also store a list of the explicit table names mapped to list of sub key names
-            // key is explicit table id, value a list of partial key ids
+        Self::default()
+    }
+
+    /// Splits an [field::Identifier] into its dot-separated, lowercased segments.
+    fn segments(id: &field::Identifier) -> Vec<String> {
+        id.get_id().split('.').map(|s| s.to_lowercase()).collect()
+    }
+
+    /// Inserts `val` at the path described by `id`.
+    ///
+    /// Errors if an ancestor segment already holds a value (the path is
+    /// blocked by an existing leaf) or if the final segment already holds a
+    /// value (the key is already set).
+    fn insert(&mut self, id: &field::Identifier, val: field::Value, pos: Pos) -> Result<(), CfgError> {
+        let segments = Self::segments(id);
+        let (last, init) = segments.split_last().expect("identifier has at least one segment");
+
+        let mut node = &mut self.root;
+        for seg in init {
+            if node.value.is_some() {
+                return Err(CfgError::KeyPathBlocked(pos, id.get_id().to_string()));
+            }
+            node = node.children.entry(seg.clone()).or_default();
+        }
+        if node.value.is_some() {
+            return Err(CfgError::KeyPathBlocked(pos, id.get_id().to_string()));
+        }
+
+        let leaf = node.children.entry(last.clone()).or_default();
+        if leaf.value.is_some() {
+            return Err(CfgError::KeyAlreadySet(pos, id.get_id().to_string()));
+        }
+        if leaf.children.is_empty() == false {
+            return Err(CfgError::KeyPathBlocked(pos, id.get_id().to_string()));
+        }
+        leaf.value = Some(val);
+        Ok(())
+    }
+
+    /// Looks up the value stored at the dotted path `s`.
+    fn get(&self, s: &str) -> Option<&field::Value> {
+        let mut node = &self.root;
+        for seg in s.split('.') {
+            node = node.children.get(&seg.to_lowercase())?;
+        }
+        node.value.as_ref()
+    }
+
+    /// Overwrites the value already stored at `id`'s path, without the
+    /// duplicate-key check `insert` applies. Returns `false` if `id` has not
+    /// been set yet.
+    fn set(&mut self, id: &field::Identifier, val: field::Value) -> bool {
+        let segments = Self::segments(id);
+        let mut node = &mut self.root;
+        for seg in &segments {
+            match node.children.get_mut(seg) {
+                Some(n) => node = n,
+                None => return false,
+            }
+        }
+        match &node.value {
+            Some(_) => {
+                node.value = Some(val);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Iterates the immediate children of the table at dotted path `section`
+    /// that hold a value, yielding `(segment, value)` pairs in segment order.
+    ///
+    /// An empty `section` walks the fields defined outside of any table.
+    fn get_table(&self, section: &str) -> impl Iterator<Item = (&str, &field::Value)> {
+        let mut node = Some(&self.root);
+        if section.is_empty() == false {
+            for seg in section.split('.') {
+                node = node.and_then(|n| n.children.get(&seg.to_lowercase()));
+            }
+        }
+        let items: Vec<(&str, &field::Value)> = match node {
+            Some(n) => n
+                .children
+                .iter()
+                .filter_map(|(k, n)| n.value.as_ref().map(|v| (k.as_str(), v)))
+                .collect(),
+            None => Vec::new(),
+        };
+        items.into_iter()
+    }
+
+    /// Lists the names of the top-level tables (the root's immediate
+    /// children), in no particular order, for callers that need to walk
+    /// every table rather than look one up by name.
+    fn tables(&self) -> impl Iterator<Item = &str> {
+        self.root.children.keys().map(|k| k.as_str())
+    }
+}
+
+/// How a field's value was written in the original source, so [CfgLanguage::to_string]
+/// can reproduce the same quoting for an untouched field.
+#[derive(Debug, Clone, PartialEq)]
+enum QuoteStyle {
+    Unquoted,
+    Single,
+    Double,
+}
+
+/// One physical line of a cfgfile document.
+///
+/// Retaining this alongside the lookup trie lets [CfgLanguage::to_string] emit a
+/// document that matches the input nearly verbatim, and lets [CfgLanguage::set]
+/// rewrite a single field's value in place rather than discarding comments and
+/// layout by reserializing from scratch.
+#[derive(Debug, Clone, PartialEq)]
+enum DocLine {
+    Blank,
+    Comment(String),
+    /// the table name as it was written, case preserved
+    TableHeader(String),
+    /// `key` is local to its enclosing table (not yet prepended)
+    Field {
+        key: field::Identifier,
+        value: field::Value,
+        quote: QuoteStyle,
+    },
+}
+
+impl DocLine {
+    fn to_line(&self) -> String {
+        match self {
+            Self::Blank => String::new(),
+            Self::Comment(c) => c.clone(),
+            Self::TableHeader(name) => format!("[{}]", name),
+            Self::Field { key, value, quote } => match quote {
+                QuoteStyle::Unquoted => format!("{} = {}", key.get_id(), value.as_str()),
+                QuoteStyle::Single => format!("{} = '{}'", key.get_id(), value.as_str()),
+                QuoteStyle::Double => format!("{} = \"{}\"", key.get_id(), value.as_str()),
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CfgLanguage {
+    trie: CfgTrie,
+    doc: Vec<DocLine>,
+    /// dotted, lowercased key -> index into `doc`, so `set` can rewrite a
+    /// single field without rescanning the whole document
+    index: HashMap<String, usize>,
+}
+
+impl CfgLanguage {
+    pub(crate) fn new() -> Self {
+        CfgLanguage {
+            trie: CfgTrie::new(),
+            doc: Vec::new(),
+            index: HashMap::new(),
         }
     }
 
     /// Access the value behind a key.
     pub fn get(&self, s: &str) -> Option<&field::Value> {
-        self.map.get(&field::Identifier::from_str(s).expect("invalid key format"))
+        self.trie.get(s)
+    }
+
+    /// Enumerates the immediate fields defined under `section` (e.g. `"core"`
+    /// for keys like `core.path`), without descending into nested tables.
+    pub fn get_table(&self, section: &str) -> impl Iterator<Item = (&str, &field::Value)> {
+        self.trie.get_table(section)
+    }
+
+    /// Lists the names of the top-level tables defined in this document, for
+    /// a caller that wants to walk every table without already knowing its
+    /// name (see [super::import_legacy_cfg]).
+    pub(crate) fn tables(&self) -> impl Iterator<Item = &str> {
+        self.trie.tables()
+    }
+
+    /// Access the value behind a key as a `bool`.
+    pub fn get_bool(&self, s: &str) -> Option<bool> {
+        self.get(s).map(|v| v.as_bool())
+    }
+
+    /// Access the value behind a key as an `i64`.
+    pub fn get_int(&self, s: &str) -> Option<i64> {
+        self.get(s).and_then(|v| v.as_int().ok())
+    }
+
+    /// Access the value behind a key as a list, splitting on `,` and
+    /// respecting quoted elements (see [field::Value::as_quoted_vec]).
+    pub fn get_list(&self, s: &str) -> Option<Vec<String>> {
+        self.get(s).map(|v| v.as_quoted_vec(','))
     }
 
-    /// Given a stream of tokens, build up hashmap according to the grammar.
-    fn parse(tokens: Vec::<Symbol>) -> Result<HashMap::<field::Identifier, field::Value>, CfgError> {
+    /// Rewrites the value of an existing field in place, preserving its
+    /// position, quoting style, and every surrounding comment and blank line.
+    /// Returns `false` if `key` has not been set.
+    pub fn set(&mut self, key: &str, value: field::Value) -> bool {
+        let idx = match self.index.get(&key.to_lowercase()) {
+            Some(i) => *i,
+            None => return false,
+        };
+        if let DocLine::Field { value: slot, .. } = &mut self.doc[idx] {
+            *slot = value.clone();
+        }
+        if let Ok(id) = field::Identifier::from_str(key) {
+            // the trie's case-insensitive equality means re-inserting at the
+            // same path always targets the existing leaf
+            let _ = self.trie.set(&id, value);
+        }
+        true
+    }
+
+    /// Reconstructs the document as text, preserving comments, blank lines,
+    /// table header casing, and each field's original quoting style.
+    pub fn to_string(&self) -> String {
+        self.doc.iter().map(DocLine::to_line).collect::<Vec<String>>().join("\n")
+    }
+
+    /// Given a stream of tokens, build up the [CfgLanguage] according to the grammar.
+    ///
+    /// Rather than aborting on the first malformed construct, every error is
+    /// collected and parsing resynchronizes at the next line boundary so a
+    /// single load surfaces every bad line at once.
+    pub(crate) fn parse(tokens: Vec::<Symbol>) -> Result<CfgLanguage, Vec<CfgError>> {
         // track the current table name
         let mut table: Option<field::Identifier> = None;
 
-        let mut map = HashMap::new();
+        let mut lang = CfgLanguage::new();
+        let mut errors = Vec::new();
         let mut t_stream = tokens.into_iter().peekable();
         while let Some(t) = t_stream.peek() {
             match t.get_token() {
                 // define a table
                 TokenType::LBRACKET => {
-                    table = Some(CfgLanguage::build_table(&mut t_stream)?);
-                    // :todo: add this explicit table name (preserve case sense) to a different map for later saving
+                    match CfgLanguage::build_table(&mut t_stream) {
+                        Ok(id) => {
+                            lang.doc.push(DocLine::TableHeader(id.get_id().to_string()));
+                            table = Some(id);
+                        }
+                        Err(e) => {
+                            errors.push(e);
+                            CfgLanguage::resync(&mut t_stream);
+                        }
+                    }
                 }
                 // create a key
                 TokenType::LITERAL(_) => {
-                    let (key, val) = CfgLanguage::build_field(&mut t_stream)?;
-                    // add data to the hashmap (case-insensitive keys)
-                    if let Some(section) = &table {
-                        // prefix the base to the key name
-                        map.insert(key.prepend(section), val);
-                    } else {
-                        map.insert(key, val);
+                    let field_pos = t.location.clone();
+                    match CfgLanguage::build_field(&mut t_stream) {
+                        Ok((key, val, quote)) => {
+                            // prefix the base table to the key name (case-insensitive keys)
+                            let full_key = match &table {
+                                Some(section) => key.clone().prepend(section),
+                                None => key.clone(),
+                            };
+                            let dotted = full_key.get_id().to_lowercase();
+                            match lang.trie.insert(&full_key, val.clone(), field_pos) {
+                                Ok(()) => {
+                                    lang.index.insert(dotted, lang.doc.len());
+                                    lang.doc.push(DocLine::Field { key, value: val, quote });
+                                }
+                                Err(e) => errors.push(e),
+                            }
+                        }
+                        Err(e) => {
+                            errors.push(e);
+                            CfgLanguage::resync(&mut t_stream);
+                        }
+                    }
+                }
+                // comment lines are preserved verbatim, including their terminating EOL
+                TokenType::COMMENT(_) => {
+                    let comment = t_stream.next().unwrap().take_str();
+                    lang.doc.push(DocLine::Comment(comment));
+                    if let Some(next) = t_stream.peek() {
+                        if let TokenType::EOL = next.get_token() {
+                            t_stream.next();
+                        }
                     }
                 }
-                // move along in the stream
-                TokenType::COMMENT(_) | TokenType::EOL | TokenType::EOF => {
+                // a bare EOL with nothing before it is a blank line
+                TokenType::EOL => {
+                    lang.doc.push(DocLine::Blank);
+                    t_stream.next();
+                }
+                TokenType::EOF => {
                     t_stream.next();
                 }
                 _ => {
-                    panic!("unexpected token {:?}", t)
+                    let bad = t_stream.next().unwrap();
+                    errors.push(CfgError::UnexpectedToken(bad.location, bad.token));
+                    CfgLanguage::resync(&mut t_stream);
                 }
             };
         }
-        Ok(map)
+        match errors.is_empty() {
+            true => Ok(lang),
+            false => Err(errors),
+        }
+    }
+
+    /// Discards tokens up through the next `EOL`/`EOF`, giving `parse` a line
+    /// boundary to resume from after a malformed table or field.
+    fn resync(ts: &mut impl Iterator<Item = Symbol>) {
+        for t in ts {
+            match t.get_token() {
+                TokenType::EOL | TokenType::EOF => break,
+                _ => {}
+            }
+        }
     }
 
     /// FIELD ::= IDENTIFIER __=__ (BASIC_VALUE | LITERAL_VALUE)
-    fn build_field(ts: &mut impl Iterator<Item=Symbol>) -> Result<(field::Identifier, field::Value), CfgError> {
+    ///
+    /// Also reports the [QuoteStyle] the value was written with, so a
+    /// round-trip `to_string` can reproduce it.
+    fn build_field(ts: &mut impl Iterator<Item=Symbol>) -> Result<(field::Identifier, field::Value, QuoteStyle), CfgError> {
         let mut ts = ts.peekable();
         // verify identifier and do something with it
         let key = CfgLanguage::verify_identifier(ts.next().unwrap())?;
         // verify that the next token is a '='
         CfgLanguage::accept_op(ts.next().unwrap(), '=')?;
         // accept accept basic literal || quoted literal || EOL/EOF
-        let value = match ts.peek().unwrap().get_token() {
+        let (value, quote) = match ts.peek().unwrap().get_token() {
             TokenType::LITERAL(_) => {
-                field::Value::from_move(ts.next().unwrap().take_str())
+                (field::Value::from_move(ts.next().unwrap().take_str()), QuoteStyle::Unquoted)
             }
             TokenType::QUOTE(_) => {
                 // check what quote was used
@@ -170,51 +452,85 @@ impl CfgLanguage {
                 // consume the quote
                 CfgLanguage::accept_op(ts.next().unwrap(), q)?;
                 // capture the literal
-                let v = field::Value::from_move(ts.next().unwrap().take_str());
+                let inner = ts.next().unwrap().take_str();
                 // consume closing quote
                 CfgLanguage::accept_op(ts.next().unwrap(), q)?;
-                v
+
+                // a quoted segment followed directly by more content on the
+                // same line (no EOL/EOF in between) is a list with a quoted
+                // element, e.g. 'a, b', c -- rebuild the raw text verbatim,
+                // quote characters included, so a later `as_quoted_vec` can
+                // re-split it and `to_string` reproduces it untouched
+                match ts.peek().unwrap().get_token() {
+                    TokenType::LITERAL(_) | TokenType::QUOTE(_) => {
+                        let mut raw = format!("{}{}{}", q, inner, q);
+                        loop {
+                            match ts.peek().unwrap().get_token() {
+                                TokenType::LITERAL(_) => {
+                                    raw.push_str(&ts.next().unwrap().take_str());
+                                }
+                                TokenType::QUOTE(_) => {
+                                    let q = ts.peek().unwrap().get_token().as_operator().unwrap();
+                                    CfgLanguage::accept_op(ts.next().unwrap(), q)?;
+                                    let inner = ts.next().unwrap().take_str();
+                                    CfgLanguage::accept_op(ts.next().unwrap(), q)?;
+                                    raw.push(q);
+                                    raw.push_str(&inner);
+                                    raw.push(q);
+                                }
+                                _ => break,
+                            }
+                        }
+                        (field::Value::from_move(raw), QuoteStyle::Unquoted)
+                    }
+                    _ => {
+                        let quote = if q == '\'' { QuoteStyle::Single } else { QuoteStyle::Double };
+                        (field::Value::from_move(inner), quote)
+                    }
+                }
             }
             TokenType::EOL | TokenType::EOF => {
-                field::Value::from_str("").unwrap()
+                (field::Value::from_str("").unwrap(), QuoteStyle::Unquoted)
+            }
+            _ => {
+                let bad = ts.next().unwrap();
+                return Err(CfgError::UnexpectedToken(bad.location, bad.token));
             }
-            _ => panic!("invalid token when parsing literal {:?}", ts.next().unwrap())
         };
         // accept EOL or EOF
-        match ts.next().unwrap().get_token() {
-            TokenType::EOF | TokenType::EOL => Ok((key, value)),
-            _ => Err(CfgError::MissingEOL),
+        let eol = ts.next().unwrap();
+        match eol.get_token() {
+            TokenType::EOF | TokenType::EOL => Ok((key, value, quote)),
+            _ => Err(CfgError::MissingEOL(eol.location)),
         }
     }
 
     /// Consumes an operator if it is matching `c` or reports an error.
     fn accept_op(t: Symbol, c: char) -> Result<(), CfgError> {
-        if let Ok(v) = t.get_token().as_operator() {
-            if v == c {
-                Ok(())
-            } else {
-                panic!("unexpected operator {:?}", t)
-            }
-        } else {
-            panic!("unexpected token {:?}", t)
+        let pos = t.location.clone();
+        match t.get_token().as_operator() {
+            Ok(v) if v == c => Ok(()),
+            Ok(v) => Err(CfgError::InvalidOperator(pos, c, v)),
+            Err(()) => Err(CfgError::UnexpectedToken(pos, t.token)),
         }
     }
 
     /// Verify the identifier is valid. It may contain only ascii letters and numbers, dashes,
     /// and dots.
     fn verify_identifier(t: Symbol) -> Result<field::Identifier, CfgError> {
+        let pos = t.location.clone();
         match t.get_token() {
             TokenType::LITERAL(_) => {
                 match field::Identifier::from_move(t.take_str()) {
                     Ok(r) => Ok(r),
-                    Err(e) => Err(CfgError::InvalidIdentifier(e)),
+                    Err(e) => Err(CfgError::InvalidIdentifier(pos, e)),
                 }
             },
             TokenType::EOF => {
-                panic!("missing identifier")
+                Err(CfgError::MissingIdentifier(pos))
             }
             _ => {
-                panic!("unexpected token {:?}", t)
+                Err(CfgError::UnexpectedToken(pos, t.token))
             }
         }
     }
@@ -228,14 +544,15 @@ impl CfgLanguage {
         // accept ]
         CfgLanguage::accept_op(ts.next().unwrap(), ']')?;
         // accept EOL or EOF
-        match ts.next().unwrap().get_token() {
+        let eol = ts.next().unwrap();
+        match eol.get_token() {
             TokenType::EOF | TokenType::EOL => Ok(table),
-            _ => Err(CfgError::MissingEOL),
+            _ => Err(CfgError::MissingEOL(eol.location)),
         }
     }
     
     /// Given some text `s`, tokenize it according the cfg language.
-    fn tokenize(s: &str) -> Vec::<Symbol> {
+    pub(crate) fn tokenize(s: &str) -> Vec::<Symbol> {
         let mut symbols = Vec::new();
         let mut cur_pos = Pos(1, 0);
         let mut buf: String = String::new();
@@ -290,13 +607,20 @@ impl CfgLanguage {
                             };
                         }
                         '\n' => {
-                            buf = buf.trim().to_string();
-                            complete_literal(&mut symbols, &mut buf_pos, buf.trim());
-                            buf.clear();
-                            symbols.push(Symbol::new(cur_pos.clone(), TokenType::EOL));
-                            cur_pos.0 += 1;
-                            cur_pos.1 = 0;
-
+                            // a trailing '\' joins this line with the next,
+                            // so a long unquoted value can wrap across lines
+                            if buf.ends_with('\\') {
+                                buf.pop();
+                                cur_pos.0 += 1;
+                                cur_pos.1 = 0;
+                            } else {
+                                buf = buf.trim().to_string();
+                                complete_literal(&mut symbols, &mut buf_pos, buf.trim());
+                                buf.clear();
+                                symbols.push(Symbol::new(cur_pos.clone(), TokenType::EOL));
+                                cur_pos.0 += 1;
+                                cur_pos.1 = 0;
+                            }
                         }
                         _ => {
                             if (c.is_whitespace() == false) || (buf.is_empty() == false) {
@@ -352,14 +676,77 @@ impl CfgLanguage {
 }
 
 #[derive(Debug, PartialEq)]
-enum CfgError {
-    InvalidIdentifier(field::IdentifierError),
-    MissingOperator(char),
-    MissingEOL,
-    // ExpectedOperator(Token, char),
+pub(crate) enum CfgError {
+    InvalidIdentifier(Pos, field::IdentifierError),
+    /// (position, expected operator)
+    MissingOperator(Pos, char),
+    /// (position of the token that should have been an EOL/EOF)
+    MissingEOL(Pos),
     /// (position, expected, got)
     InvalidOperator(Pos, char, char),
-    // ExpectedEOL(Token),
+    /// (position, expected identifier but stream ended)
+    MissingIdentifier(Pos),
+    /// (position, the token that could not be handled here)
+    UnexpectedToken(Pos, TokenType),
+    /// (position, dotted key) - an ancestor segment of this key is already a leaf value
+    KeyPathBlocked(Pos, String),
+    /// (position, dotted key) - this key already holds a value
+    KeyAlreadySet(Pos, String),
+}
+
+impl CfgError {
+    /// References the [Pos] where this error originated.
+    fn pos(&self) -> &Pos {
+        match self {
+            Self::InvalidIdentifier(p, _) => p,
+            Self::MissingOperator(p, _) => p,
+            Self::MissingEOL(p) => p,
+            Self::InvalidOperator(p, _, _) => p,
+            Self::MissingIdentifier(p) => p,
+            Self::UnexpectedToken(p, _) => p,
+            Self::KeyPathBlocked(p, _) => p,
+            Self::KeyAlreadySet(p, _) => p,
+        }
+    }
+
+    /// Describes the failure in a single line, without any source context.
+    fn header(&self) -> String {
+        match self {
+            Self::InvalidIdentifier(_, e) => format!("invalid identifier: {}", e),
+            Self::MissingOperator(_, c) => format!("expected operator '{}'", c),
+            Self::MissingEOL(_) => "expected end of line".to_string(),
+            Self::InvalidOperator(_, want, got) => {
+                format!("expected '{}' but found '{}'", want, got)
+            }
+            Self::MissingIdentifier(_) => "expected an identifier but found end of file".to_string(),
+            Self::UnexpectedToken(_, t) => format!("unexpected token '{}'", t),
+            Self::KeyPathBlocked(_, key) => {
+                format!("key path for '{}' is blocked by an existing value", key)
+            }
+            Self::KeyAlreadySet(_, key) => format!("key '{}' is already set", key),
+        }
+    }
+
+    /// The number of characters the offending span covers, used to size the `^` underline.
+    fn width(&self) -> usize {
+        match self {
+            Self::UnexpectedToken(_, t) => t.to_string().chars().count().max(1),
+            _ => 1,
+        }
+    }
+
+    /// Renders this error as a caret-annotated snippet of the offending line in `source`.
+    pub(crate) fn render(&self, source: &str) -> String {
+        let pos = self.pos();
+        let line_text = source.lines().nth(pos.0.saturating_sub(1)).unwrap_or("");
+        let gutter = format!("{} | ", pos.0);
+        let marker = format!(
+            "{}{}",
+            " ".repeat(gutter.len() + pos.1.saturating_sub(1)),
+            "^".repeat(self.width()),
+        );
+        format!("error: {}\n{}{}\n{}", self.header(), gutter, line_text, marker)
+    }
 }
 
 #[cfg(test)]
@@ -375,8 +762,8 @@ mod test {
             Symbol::new(Pos(1, 3), TokenType::LITERAL("value".to_owned())),
             Symbol::new(Pos(1, 4), TokenType::EOL),
         ];
-        assert_eq!(CfgLanguage::build_field(&mut v.into_iter()).unwrap(), 
-            (field::Identifier::from_str("key1").unwrap(), field::Value::from_str("value").unwrap()));
+        assert_eq!(CfgLanguage::build_field(&mut v.into_iter()).unwrap(),
+            (field::Identifier::from_str("key1").unwrap(), field::Value::from_str("value").unwrap(), QuoteStyle::Unquoted));
             
         // only one key can be defined on a line (missing eol)
         let v = vec![
@@ -557,6 +944,26 @@ key2 = value2";
         ]);
     }
 
+    #[test]
+    fn line_continuation() {
+        // a trailing '\' joins the value with the next physical line
+        let s = "\
+key = nor_gate,\\
+and_gate,mux_2x1
+";
+        assert_eq!(CfgLanguage::tokenize(s), vec![
+            Symbol::new(Pos(1, 1), TokenType::LITERAL("key".to_owned())),
+            Symbol::new(Pos(1, 5), TokenType::ASSIGNMENT),
+            Symbol::new(Pos(1, 7), TokenType::LITERAL("nor_gate,and_gate,mux_2x1".to_owned())),
+            Symbol::new(Pos(2, 17), TokenType::EOL),
+            Symbol::new(Pos(3, 1), TokenType::EOF),
+        ]);
+
+        // a field built from a continued value behaves like a single line
+        let config = CfgLanguage::parse(CfgLanguage::tokenize("key = a,\\\nb\n")).unwrap();
+        assert_eq!(config.get("key"), Some(&field::Value::from_str("a,b").unwrap()));
+    }
+
     #[test]
     fn comments() {
         let s = "\
@@ -596,14 +1003,126 @@ course=EEL4712C: Digital Design
 key     = 
 ";
         let tokens = CfgLanguage::tokenize(s);
-        let map = CfgLanguage::parse(tokens).unwrap();
-        let config = CfgLanguage {
-            map: map,
-        };
+        let config = CfgLanguage::parse(tokens).unwrap();
 
         assert_eq!(config.get("core.path"), Some(&field::Value::from_str("/users/chase/hdl").unwrap()));
         assert_eq!(config.get("core.user"), Some(&field::Value::from_str("Chase Ruskin ").unwrap()));
         assert_eq!(config.get("table.key"), Some(&field::Value::from_str("").unwrap()));
         assert_eq!(config.get("plugin.ghdl.execute"), None);
     }
+
+    #[test]
+    fn get_table_walks_immediate_children() {
+        let s = "\
+[core]
+path = /users/chase/hdl
+user = chase
+
+[core.vendor]
+name = amd
+";
+        let config = CfgLanguage::parse(CfgLanguage::tokenize(s)).unwrap();
+
+        let mut fields: Vec<(&str, &str)> = config
+            .get_table("core")
+            .map(|(k, v)| (k, v.as_vec(',')[0]))
+            .collect();
+        fields.sort();
+        assert_eq!(fields, vec![("path", "/users/chase/hdl"), ("user", "chase")]);
+
+        // "vendor" is a sub-table, not a field, so it is absent from "core"'s fields
+        assert!(config.get_table("core").any(|(k, _)| k == "vendor") == false);
+        assert_eq!(config.get("core.vendor.name"), Some(&field::Value::from_str("amd").unwrap()));
+    }
+
+    #[test]
+    fn insert_rejects_blocked_and_duplicate_paths() {
+        let mut trie = CfgTrie::new();
+        let pos = Pos(1, 1);
+
+        let core_user = field::Identifier::from_str("core.user").unwrap();
+        trie.insert(&core_user, field::Value::from_str("chase").unwrap(), pos.clone()).unwrap();
+
+        // re-setting the same key is rejected
+        assert!(trie.insert(&core_user, field::Value::from_str("other").unwrap(), pos.clone()).is_err());
+
+        // descending through an existing leaf ("core.user" as a table) is rejected
+        let blocked = field::Identifier::from_str("core.user.alias").unwrap();
+        assert!(trie.insert(&blocked, field::Value::from_str("cr").unwrap(), pos).is_err());
+    }
+
+    #[test]
+    fn parse_recovers_and_reports_every_bad_line() {
+        // the third line has an invalid key (it begins with a digit) and the
+        // fourth is a stray ']'; parsing resumes on the next line after each
+        // one, so both are reported from a single parse
+        let s = "\
+[core]
+path = /users/chase/hdl
+9bad key = oops
+]
+user = chase
+";
+        let tokens = CfgLanguage::tokenize(s);
+        let errs = CfgLanguage::parse(tokens).unwrap_err();
+        assert_eq!(errs.len(), 2);
+
+        // re-tokenizing and parsing a clean file still yields zero errors
+        let tokens = CfgLanguage::tokenize("[core]\npath = /users/chase/hdl\n");
+        assert!(CfgLanguage::parse(tokens).is_ok());
+    }
+
+    #[test]
+    fn to_string_round_trips_comments_and_layout() {
+        let s = "\
+; orbit configuration file
+
+[core]
+path = /users/chase/hdl
+user = 'Chase Ruskin'";
+        let config = CfgLanguage::parse(CfgLanguage::tokenize(s)).unwrap();
+        assert_eq!(config.to_string(), s);
+    }
+
+    #[test]
+    fn set_rewrites_only_the_touched_field() {
+        let s = "\
+; orbit configuration file
+[core]
+path = /users/chase/hdl
+user = 'Chase Ruskin'";
+        let mut config = CfgLanguage::parse(CfgLanguage::tokenize(s)).unwrap();
+
+        assert!(config.set("core.path", field::Value::from_str("/home/chase/hdl").unwrap()));
+        assert_eq!(config.get("core.path"), Some(&field::Value::from_str("/home/chase/hdl").unwrap()));
+
+        // the comment, table header, and sibling field are untouched, and the
+        // single quoting on "user" is preserved
+        assert_eq!(config.to_string(), "\
+; orbit configuration file
+[core]
+path = /home/chase/hdl
+user = 'Chase Ruskin'");
+
+        // setting a key that was never defined is a no-op that reports failure
+        assert!(config.set("core.missing", field::Value::from_str("x").unwrap()) == false);
+    }
+
+    #[test]
+    fn quoted_list_value() {
+        let s = "\
+[core]
+name = 'a, b', c
+count = 14
+enabled = true";
+        let config = CfgLanguage::parse(CfgLanguage::tokenize(s)).unwrap();
+
+        // the quote around the first element does not split the list
+        assert_eq!(config.get_list("core.name"), Some(vec!["a, b".to_string(), "c".to_string()]));
+        assert_eq!(config.get_int("core.count"), Some(14));
+        assert_eq!(config.get_bool("core.enabled"), Some(true));
+
+        // the raw text, quotes included, round-trips untouched
+        assert_eq!(config.to_string(), s);
+    }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-#[derive(Debug, Hash)]
+#[derive(Debug, Clone, Hash)]
 pub struct Identifier {
     id: String,
 }
@@ -99,7 +99,7 @@ impl std::fmt::Display for IdentifierError {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Value {
     value: String,
 }
@@ -128,6 +128,11 @@ impl Value {
         self.value.split_terminator(sep).collect()
     }
 
+    /// Views the raw, unsplit value text.
+    pub fn as_str(&self) -> &str {
+        self.value.as_ref()
+    }
+
     /// Returns true iff value is "YES", "ON", "1", "TRUE", or "ENABLE".
     pub fn as_bool(&self) -> bool {
         match self.value.to_lowercase().as_ref() {
@@ -135,6 +140,42 @@ impl Value {
             _ => false,
         }
     }
+
+    /// Parses the value as an integer.
+    pub fn as_int(&self) -> Result<i64, std::num::ParseIntError> {
+        self.value.trim().parse()
+    }
+
+    /// Splits the value on top-level occurrences of `sep`, trimming each
+    /// element and dropping a trailing empty element left by a dangling
+    /// separator.
+    ///
+    /// Unlike [Value::as_vec], a `sep` found between a matching pair of `'`
+    /// or `"` does not split the list, so a value like `'a, b', c` yields
+    /// `["a, b", "c"]` instead of `["'a", " b'", " c"]`.
+    pub fn as_quoted_vec(&self, sep: char) -> Vec<String> {
+        if self.value.is_empty() {
+            return Vec::new();
+        }
+        let mut segments: Vec<String> = Vec::new();
+        let mut cur = String::new();
+        let mut quote: Option<char> = None;
+        for c in self.value.chars() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => cur.push(c),
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if c == sep => segments.push(std::mem::take(&mut cur)),
+                None => cur.push(c),
+            }
+        }
+        // a dangling separator terminates the list rather than introducing a
+        // trailing empty element
+        if self.value.ends_with(sep) == false || cur.is_empty() == false {
+            segments.push(cur);
+        }
+        segments.iter().map(|s| s.trim().to_string()).collect()
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -254,4 +295,36 @@ mod test {
         let v = Value::from_str(",profile/eel4712c/config.ini").unwrap();
         assert_eq!(v.as_vec(','), ["", "profile/eel4712c/config.ini"]);
     }
+
+    #[test]
+    fn as_int() {
+        let v = Value::from_str("14").unwrap();
+        assert_eq!(v.as_int(), Ok(14));
+
+        let v = Value::from_str(" 14 ").unwrap();
+        assert_eq!(v.as_int(), Ok(14));
+
+        let v = Value::from_str("nor_gate").unwrap();
+        assert!(v.as_int().is_err());
+    }
+
+    #[test]
+    fn as_quoted_vec() {
+        let v = Value::from_str("nor_gate,and_gate,mux_2x1").unwrap();
+        assert_eq!(v.as_quoted_vec(','), ["nor_gate", "and_gate", "mux_2x1"]);
+
+        let v = Value::from_str("").unwrap();
+        assert_eq!(v.as_quoted_vec(','), Vec::<String>::new());
+
+        // a separator inside a quoted segment does not split the list
+        let v = Value::from_str("'a, b', c").unwrap();
+        assert_eq!(v.as_quoted_vec(','), ["a, b", "c"]);
+
+        let v = Value::from_str("\"x, y\",z").unwrap();
+        assert_eq!(v.as_quoted_vec(','), ["x, y", "z"]);
+
+        // trailing dangling separator does not introduce an empty element
+        let v = Value::from_str("a,b,").unwrap();
+        assert_eq!(v.as_quoted_vec(','), ["a", "b"]);
+    }
 }
\ No newline at end of file
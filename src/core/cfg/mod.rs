@@ -0,0 +1,83 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A from-scratch ini-like file format ([cfgfile]/[field]) that predates
+//! `config.toml`'s toml_edit-backed format in [super::config]. Nothing reads
+//! or writes it anymore, but hand-maintained copies of it still turn up
+//! alongside old `orbit` installs, so [import_legacy_cfg] lets `orbit
+//! config --import` upgrade one into the current format instead of
+//! requiring it to be retyped by hand.
+
+pub(crate) mod cfgfile;
+pub mod field;
+
+use cfgfile::{CfgError, CfgLanguage};
+
+/// Parses a legacy cfgfile document and flattens it into `(table, key,
+/// value)` triples, one per field in every top-level table, ready to be
+/// replayed through [super::config::ConfigDocument::set] the same way a
+/// `--set table.key=value` entry is.
+///
+/// Returns the caret-annotated [CfgError::render] of every problem found, if
+/// the document fails to parse.
+pub fn import_legacy_cfg(source: &str) -> Result<Vec<(String, String, String)>, String> {
+    let tokens = CfgLanguage::tokenize(source);
+    let cfg = CfgLanguage::parse(tokens).map_err(|errs: Vec<CfgError>| {
+        errs.iter()
+            .map(|e| e.render(source))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    })?;
+
+    let mut entries = Vec::new();
+    for table in cfg.tables() {
+        for (key, value) in cfg.get_table(table) {
+            entries.push((table.to_string(), key.to_string(), value.as_str().to_string()));
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn import_legacy_cfg_flattens_tables() {
+        let s = "\
+[general]
+name = value
+[build]
+command = make";
+        let mut entries = import_legacy_cfg(s).unwrap();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("build".to_string(), "command".to_string(), "make".to_string()),
+                ("general".to_string(), "name".to_string(), "value".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn import_legacy_cfg_reports_parse_errors() {
+        let s = "name = ]";
+        let err = import_legacy_cfg(s).unwrap_err();
+        assert!(err.contains("error:"));
+    }
+}
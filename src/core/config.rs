@@ -15,10 +15,13 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use crate::core::alias::Aliases;
 use crate::core::lang::vhdl::format::VhdlFormat;
 use crate::core::manifest::FromFile;
 use crate::core::protocol::Protocol;
 use crate::core::protocol::Protocols;
+use crate::core::source::Mirror;
+use crate::core::source::Mirrors;
 use crate::core::target::{Target, Targets};
 use crate::error::Error;
 use crate::error::LastError;
@@ -536,6 +539,13 @@ pub struct Config {
     target: Option<Targets>,
     protocol: Option<Protocols>,
     channel: Option<Channels>,
+    mirror: Option<Mirrors>,
+    /// User-defined/overriding glob patterns for the named file-type registry
+    /// (see [crate::util::filetype]), e.g. `filetype.xdc = ["*.xdc"]`.
+    filetype: Option<HashMap<String, Vec<String>>>,
+    /// User-defined command aliases (see [crate::core::alias]), e.g.
+    /// `alias.b = "build --release"`.
+    alias: Option<Aliases>,
     #[serde(rename = "vhdl-format")]
     vhdl_format: Option<VhdlFormat>,
     #[serde(rename = "systemverilog-format")]
@@ -556,6 +566,9 @@ impl Config {
             target: None,
             channel: None,
             protocol: None,
+            mirror: None,
+            filetype: None,
+            alias: None,
             vhdl_format: None,
             systemverilog_format: None,
             general: None,
@@ -606,6 +619,28 @@ impl Config {
             }
             None => self.env = rhs.env,
         }
+        // combine '[filetype]' table
+        match &mut self.filetype {
+            Some(v) => {
+                let temp = rhs.filetype.unwrap_or(HashMap::new());
+                for (name, patterns) in temp {
+                    v.entry(name).or_insert_with(Vec::new).extend(patterns);
+                }
+            }
+            None => self.filetype = rhs.filetype,
+        }
+        // combine '[alias]' table
+        match &mut self.alias {
+            Some(v) => {
+                let temp = rhs.alias.unwrap_or(HashMap::new());
+                for (key, val) in temp {
+                    if v.contains_key(&key) == false {
+                        v.insert(key, val);
+                    }
+                }
+            }
+            None => self.alias = rhs.alias,
+        }
         // combine '[build]' table
         match &mut self.build {
             Some(v) => v.merge(rhs.build),
@@ -646,6 +681,11 @@ impl Config {
             Some(v) => v.append(&mut rhs.protocol.unwrap_or(Vec::new())),
             None => self.protocol = rhs.protocol,
         }
+        // combine '[[mirror]]' array
+        match &mut self.mirror {
+            Some(v) => v.append(&mut rhs.mirror.unwrap_or(Vec::new())),
+            None => self.mirror = rhs.mirror,
+        }
     }
 
     pub fn get_includes(&self) -> Vec<&PathBuf> {
@@ -699,6 +739,14 @@ impl Config {
         map
     }
 
+    /// Collects the configured source-replacement mirrors, in precedence order.
+    pub fn get_mirrors(&self) -> &[Mirror] {
+        match &self.mirror {
+            Some(m) => m.as_slice(),
+            None => &[],
+        }
+    }
+
     pub fn get_targets(&self) -> HashMap<&str, &Target> {
         let mut map = HashMap::new();
 
@@ -740,6 +788,17 @@ impl Config {
     pub fn get_general(&self) -> Option<&General> {
         self.general.as_ref()
     }
+
+    /// References the user-defined/overriding patterns for the named
+    /// [crate::util::filetype] registry, if any were configured.
+    pub fn get_filetypes(&self) -> Option<&HashMap<String, Vec<String>>> {
+        self.filetype.as_ref()
+    }
+
+    /// References the user-defined command aliases, if any were configured.
+    pub fn get_aliases(&self) -> Option<&Aliases> {
+        self.alias.as_ref()
+    }
 }
 
 impl FromStr for Config {
@@ -5,6 +5,7 @@ use crate::error::{Error, Hint};
 use crate::util::anyerror::AnyError;
 use crate::util::anyerror::Fault;
 use crate::util::environment::{self, Environment, ORBIT_WIN_LITERAL_CMD};
+use crate::util::filesystem;
 use crate::util::filesystem::Standardize;
 use std::collections::HashMap;
 use std::env;
@@ -36,8 +37,10 @@ pub struct Context {
     channels_path: PathBuf,
     /// The parent path to the current ip `Orbit.toml` manifest file.
     ip_path: Option<PathBuf>,
-    /// Directory name for the intermediate build processes and outputs.    
+    /// Directory name for the intermediate build processes and outputs.
     build_dir: String,
+    /// Number of worker threads to use for parallel directory walks.
+    threads: usize,
     /// Language support mode.
     languages: Languages,
     /// Flattened view of the current configuration settings.
@@ -64,6 +67,7 @@ impl Context {
             all_configs: Configs::new(),
             config: Config::new(),
             build_dir: String::new(),
+            threads: filesystem::default_thread_count(),
             languages: Languages::default(),
         }
     }
@@ -380,6 +384,21 @@ impl Context {
         Ok(self)
     }
 
+    /// Re-evaluates the number of worker threads to use for parallel directory
+    /// walks (see [filesystem::default_thread_count]).
+    ///
+    /// Call this after the environment is fully configured so a late-set
+    /// [environment::ORBIT_SINGLE_THREADED] is honored.
+    pub fn threads(mut self) -> Context {
+        self.threads = filesystem::default_thread_count();
+        self
+    }
+
+    /// References the number of worker threads to use for parallel directory walks.
+    pub fn get_threads(&self) -> usize {
+        self.threads
+    }
+
     pub fn select_target(
         &self,
         target: &Option<String>,
@@ -30,6 +30,7 @@ impl From<HashMap<String, Style>> for Filesets {
                 .map(|(n, p)| Fileset {
                     name: n,
                     pattern: p,
+                    exclude: Vec::new(),
                 })
                 .collect(),
         )
@@ -40,6 +41,11 @@ impl From<HashMap<String, Style>> for Filesets {
 pub struct Fileset {
     name: String,
     pattern: Style,
+    /// Glob patterns that, if matched, remove a file from this fileset even
+    /// though `pattern` matched it (e.g. `rtl/**` combined with an exclude
+    /// of `**/*_old.vhd` or `sim/scratch/**`).
+    #[serde(default)]
+    exclude: Vec<Style>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -142,23 +148,45 @@ impl FromStr for Fileset {
         if result == None {
             return Err(Self::Err::MissingSeparator('='));
         }
-        let (name, pattern) = result.unwrap();
+        let (name, patterns) = result.unwrap();
         // name cannot be empty
         if name.is_empty() {
             return Err(Self::Err::EmptyName);
         }
-        Ok(Fileset {
-            pattern: match Pattern::new(pattern) {
-                // pattern must not be empty
-                Ok(p) => {
-                    if p.as_str().is_empty() {
+
+        // a comma-separated list of patterns; a leading '!' marks an exclude
+        // (gitignore-style negation), e.g. `rtl=**/*.vhd,!**/*_old.vhd`
+        let mut pattern: Option<Pattern> = None;
+        let mut exclude = Vec::new();
+        for term in patterns.split(',') {
+            match term.strip_prefix('!') {
+                Some(term) => {
+                    if term.is_empty() {
                         return Err(Self::Err::EmptyPattern);
-                    } else {
-                        p.into()
+                    }
+                    match Pattern::new(term) {
+                        Ok(p) => exclude.push(p.into()),
+                        Err(e) => return Err(Self::Err::PatternError(term.to_string(), e)),
                     }
                 }
-                Err(e) => return Err(Self::Err::PatternError(pattern.to_string(), e)),
+                None => {
+                    if term.is_empty() {
+                        return Err(Self::Err::EmptyPattern);
+                    }
+                    match Pattern::new(term) {
+                        Ok(p) => pattern = Some(p),
+                        Err(e) => return Err(Self::Err::PatternError(term.to_string(), e)),
+                    }
+                }
+            }
+        }
+
+        Ok(Fileset {
+            pattern: match pattern {
+                Some(p) => p.into(),
+                None => return Err(Self::Err::EmptyPattern),
             },
+            exclude: exclude,
             name: Self::standardize_name(name),
         })
     }
@@ -170,6 +198,7 @@ impl Fileset {
         Fileset {
             name: String::new(),
             pattern: Pattern::new("*").unwrap().into(),
+            exclude: Vec::new(),
         }
     }
 
@@ -193,6 +222,20 @@ impl Fileset {
         Ok(self)
     }
 
+    /// Adds a glob pattern that removes a file from this fileset even when
+    /// [Self::pattern] matches it.
+    ///
+    /// Follows the same implicit `**/` recursive-directory prefix inference
+    /// as [Self::pattern].
+    pub fn exclude(mut self, p: &str) -> Result<Self, PatternError> {
+        let prefix = match p.get(0..1) {
+            Some(".") => "",
+            _ => "**/",
+        };
+        self.exclude.push(Pattern::new(&(prefix.to_owned() + p))?.into());
+        Ok(self)
+    }
+
     /// Standardizes the name to be UPPER-AND-HYPHENS.
     ///
     /// The returned string is its own data (cloned from `s`).
@@ -201,6 +244,14 @@ impl Fileset {
     }
 
     /// Uses the given pattern to return a set of build files.
+    ///
+    /// A file matching [Self::pattern] is still dropped if it also matches
+    /// any pattern in [Self::exclude].
+    ///
+    /// This filters an already-gathered file list rather than walking the
+    /// directory tree itself (that single walk, `filesystem::gather_current_files`,
+    /// is shared by every fileset and the HDL graph), so an exclude pattern
+    /// prunes matches here rather than skipping a subtree during the walk.
     pub fn collect_files<'a>(&self, files: &'a [String]) -> Vec<&'a String> {
         let match_opts = glob::MatchOptions {
             case_sensitive: false,
@@ -211,11 +262,17 @@ impl Fileset {
         files
             .iter()
             .filter_map(|f| {
-                if self.pattern.inner().matches_with(&f, match_opts) == true {
-                    Some(f)
-                } else {
-                    None
+                if self.pattern.inner().matches_with(&f, match_opts) == false {
+                    return None;
                 }
+                if self
+                    .exclude
+                    .iter()
+                    .any(|ex| ex.inner().matches_with(&f, match_opts))
+                {
+                    return None;
+                }
+                Some(f)
             })
             .collect()
     }
@@ -349,6 +406,7 @@ mod test {
             Fileset {
                 name: String::from("HELLO-WORLD"),
                 pattern: Pattern::new("**/*.txt").unwrap().into(),
+                exclude: Vec::new(),
             }
         );
 
@@ -361,6 +419,7 @@ mod test {
             Fileset {
                 name: String::from("HELLO-WORLD"),
                 pattern: Pattern::new("./some/specific/path.txt").unwrap().into(),
+                exclude: Vec::new(),
             }
         );
     }
@@ -373,7 +432,8 @@ mod test {
             fset.unwrap(),
             Fileset {
                 name: String::from("XSIM-CFG"),
-                pattern: Pattern::new("*.wcfg").unwrap().into()
+                pattern: Pattern::new("*.wcfg").unwrap().into(),
+                exclude: Vec::new(),
             }
         );
 
@@ -394,6 +454,49 @@ mod test {
         assert_eq!(fset.is_err(), true); // pattern error
     }
 
+    #[test]
+    fn fset_from_str_with_exclude() {
+        let s = "rtl=**/*.vhd,!**/*_old.vhd,!sim/scratch/**";
+        let fset = Fileset::from_str(s).unwrap();
+        assert_eq!(
+            fset,
+            Fileset {
+                name: String::from("RTL"),
+                pattern: Pattern::new("**/*.vhd").unwrap().into(),
+                exclude: vec![
+                    Pattern::new("**/*_old.vhd").unwrap().into(),
+                    Pattern::new("sim/scratch/**").unwrap().into(),
+                ],
+            }
+        );
+
+        // an exclude term with nothing after the '!' is an empty pattern
+        let s = "rtl=**/*.vhd,!";
+        let fset = Fileset::from_str(s);
+        assert_eq!(fset.is_err(), true);
+    }
+
+    #[test]
+    fn collect_files_respects_exclude() {
+        let fset = Fileset::new()
+            .name("rtl")
+            .pattern("*.vhd")
+            .unwrap()
+            .exclude("*_old.vhd")
+            .unwrap();
+
+        let files = vec![
+            String::from("adder.vhd"),
+            String::from("adder_old.vhd"),
+            String::from("adder.sv"),
+        ];
+
+        assert_eq!(
+            fset.collect_files(&files),
+            vec![&String::from("adder.vhd")]
+        );
+    }
+
     #[test]
     fn std_name() {
         let s: &str = "VHDL-RTL";
@@ -22,6 +22,7 @@ use crate::error::LastError;
 use crate::util::anyerror::AnyError;
 use crate::util::anyerror::CodeFault;
 use crate::util::anyerror::Fault;
+use crate::util::environment;
 use std::path::PathBuf;
 
 use super::iparchive::IpArchive;
@@ -328,21 +329,34 @@ impl Ip {
 
     /// Finds all Manifest files available in the provided path `path`.
     ///
-    /// Errors if on filesystem problems.
+    /// Errors if on filesystem problems. Once the manifest paths are found, the
+    /// ip at each one is loaded in parallel via `rayon` (set
+    /// [crate::util::environment::ORBIT_SINGLE_THREADED] to fall back to a
+    /// serial scan for debugging).
     fn detect_all_sub(
         path: &PathBuf,
         name: &str,
         is_exclusive: bool,
         is_working: bool,
     ) -> Result<Vec<Self>, Fault> {
-        let mut result = Vec::new();
         // walk the ORBIT_PATH directory @TODO recursively walk inner directories until hitting first 'Orbit.toml' file
-        for mut entry in manifest::find_file(&path, &name, is_exclusive)? {
-            // remove the manifest file to access the ip's root directory
-            entry.pop();
-            result.push(Ip::load(entry, is_working)?);
+        let dirs: Vec<PathBuf> = manifest::find_file(&path, &name, is_exclusive)?
+            .into_iter()
+            .map(|mut entry| {
+                // remove the manifest file to access the ip's root directory
+                entry.pop();
+                entry
+            })
+            .collect();
+
+        if environment::is_single_threaded() == true {
+            return dirs.into_iter().map(|dir| Ip::load(dir, is_working)).collect();
         }
-        Ok(result)
+
+        use rayon::prelude::*;
+        dirs.into_par_iter()
+            .map(|dir| Ip::load(dir, is_working))
+            .collect()
     }
 
     /// Finds all IP manifest files along the provided path `path`.
@@ -467,7 +481,12 @@ impl Ip {
     ///
     /// Changes the current working directory to the root for consistent computation.
     pub fn compute_checksum(dir: &PathBuf) -> Sha256Hash {
-        let ip_files = crate::util::filesystem::gather_current_files(&dir, true);
+        let ip_files = crate::util::filesystem::gather_current_files(
+            &dir,
+            true,
+            crate::util::filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        );
         let checksum = crate::util::checksum::checksum(&ip_files, &dir);
         checksum
     }
@@ -566,6 +585,20 @@ impl Ip {
         force: bool,
         lang_mode: &Language,
         hide_private: bool,
+    ) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
+        self.collect_units_for_targets(force, lang_mode, hide_private, &HashSet::new())
+    }
+
+    /// Same as [Self::collect_units], but additionally narrows the result
+    /// down to units tagged for one of `targets` (see
+    /// [lang::collect_units]), mirroring how `orbit build --target <name>`
+    /// scopes a single invocation to one tool/flow.
+    pub fn collect_units_for_targets(
+        &self,
+        force: bool,
+        lang_mode: &Language,
+        hide_private: bool,
+        targets: &HashSet<String>,
     ) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
         let public_list = self.into_public_list();
         // try to read from metadata file
@@ -576,7 +609,7 @@ impl Ip {
                 // collect all files
                 let files = self.gather_current_files();
 
-                let mut map = lang::collect_units(&files, lang_mode)?;
+                let mut map = lang::collect_units(&files, lang_mode, targets)?;
 
                 // work to remove files that are totally private
                 if public_list.exists() == true {
@@ -679,7 +712,12 @@ impl Ip {
             },
             Err(_) => None,
         };
-        filesystem::gather_current_files(&self.root, false)
+        filesystem::gather_current_files(
+            &self.root,
+            false,
+            filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        )
             .into_iter()
             .filter(|f| match &inc {
                 Some(vip) => vip.is_included(f.as_ref()) == true,
@@ -712,12 +750,29 @@ use std::path::Path;
 
 const SPEC_DELIM: &str = ":";
 
-#[derive(Debug, PartialEq, Hash, Eq, Clone, PartialOrd)]
-pub struct IpSpec(PkgPart, Version);
+/// Alternate single-token spec delimiters, accepted anywhere [SPEC_DELIM]
+/// is: `#` marks a partial version ("latest matching that prefix"), `@`
+/// pins an explicit, fully qualified version. Whichever delimiter a spec
+/// was parsed with is remembered (see [split_spec]) so it round-trips back
+/// to the same string, e.g. for lockfile display.
+const SPEC_DELIM_PARTIAL: char = '#';
+const SPEC_DELIM_EXACT: char = '@';
+
+/// Splits `s` on the last occurrence of [SPEC_DELIM], [SPEC_DELIM_PARTIAL],
+/// or [SPEC_DELIM_EXACT], returning the name, the delimiter found, and the
+/// version text.
+fn split_spec(s: &str) -> Option<(&str, char, &str)> {
+    let delim = SPEC_DELIM.chars().next().unwrap();
+    let idx = s.rfind(|c| c == delim || c == SPEC_DELIM_PARTIAL || c == SPEC_DELIM_EXACT)?;
+    Some((&s[..idx], s[idx..].chars().next().unwrap(), &s[idx + 1..]))
+}
+
+#[derive(Debug, Clone)]
+pub struct IpSpec(PkgPart, Version, char);
 
 impl IpSpec {
     pub fn new(id: PkgPart, version: Version) -> Self {
-        Self(id, version)
+        Self(id, version, SPEC_DELIM.chars().next().unwrap())
     }
 
     pub fn get_name(&self) -> &PkgPart {
@@ -732,17 +787,39 @@ impl IpSpec {
         PartialIpSpec(
             self.0.clone(),
             AnyVersion::Specific(self.1.to_partial_version()),
+            self.2,
         )
     }
 }
 
+impl PartialEq for IpSpec {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+impl Eq for IpSpec {}
+
+impl std::hash::Hash for IpSpec {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+        self.1.hash(state);
+    }
+}
+
+impl PartialOrd for IpSpec {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (&self.0, &self.1).partial_cmp(&(&other.0, &other.1))
+    }
+}
+
 impl FromStr for IpSpec {
     type Err = Fault;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // split by delimiter
-        match s.rsplit_once(SPEC_DELIM) {
-            Some((n, v)) => Ok(Self::new(PkgPart::from_str(n)?, Version::from_str(v)?)),
+        match split_spec(s) {
+            Some((n, delim, v)) => Ok(Self(PkgPart::from_str(n)?, Version::from_str(v)?, delim)),
             None => Err(Box::new(AnyError(format!(
                 "missing specification delimiter {}",
                 SPEC_DELIM
@@ -753,13 +830,13 @@ impl FromStr for IpSpec {
 
 impl std::fmt::Display for IpSpec {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}{}", self.get_name(), SPEC_DELIM, self.get_version())
+        write!(f, "{}{}{}", self.get_name(), self.2, self.get_version())
     }
 }
 
 impl From<(PkgPart, Version)> for IpSpec {
     fn from(value: (PkgPart, Version)) -> Self {
-        Self(value.0, value.1)
+        Self(value.0, value.1, SPEC_DELIM.chars().next().unwrap())
     }
 }
 
@@ -808,12 +885,31 @@ impl Serialize for IpSpec {
 
 use crate::core::version::AnyVersion;
 
-#[derive(Debug, PartialEq, Clone, Hash, Eq)]
-pub struct PartialIpSpec(PkgPart, AnyVersion);
+#[derive(Debug, Clone)]
+pub struct PartialIpSpec(PkgPart, AnyVersion, char);
+
+impl PartialEq for PartialIpSpec {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+impl Eq for PartialIpSpec {}
+
+impl std::hash::Hash for PartialIpSpec {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+        self.1.hash(state);
+    }
+}
 
 impl PartialIpSpec {
     pub fn new(name: PkgPart, version: PartialVersion) -> Self {
-        Self(name, AnyVersion::Specific(version))
+        Self(
+            name,
+            AnyVersion::Specific(version),
+            SPEC_DELIM.chars().next().unwrap(),
+        )
     }
 
     pub fn get_name(&self) -> &PkgPart {
@@ -832,6 +928,19 @@ impl PartialIpSpec {
     }
 }
 
+/// Parses `s` as a bare package name, erroring if it carries an embedded
+/// version (`:`, `#`, or `@`) for a context that does not accept one (e.g.
+/// a command that only ever operates on a package name, not a spec).
+pub fn parse_bare_name(s: &str) -> Result<PkgPart, AnyError> {
+    if split_spec(s).is_some() {
+        return Err(AnyError(format!(
+            "expecting a package name, but found a version attached to {:?}",
+            s
+        )));
+    }
+    PkgPart::from_str(s).map_err(|e| AnyError(e.to_string()))
+}
+
 impl<'de> Deserialize<'de> for PartialIpSpec {
     fn deserialize<D>(deserializer: D) -> Result<PartialIpSpec, D::Error>
     where
@@ -874,18 +983,31 @@ impl FromStr for PartialIpSpec {
     type Err = AnyError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.rsplit_once(SPEC_DELIM) {
+        match split_spec(s) {
             // split by delimiter (beginning from rhs)
-            Some((n, v)) => Ok(Self(
-                match PkgPart::from_str(n) {
+            Some((n, delim, v)) => {
+                let name = match PkgPart::from_str(n) {
                     Ok(p) => p,
                     Err(e) => return Err(AnyError(e.to_string())),
-                },
-                match AnyVersion::from_str(v) {
+                };
+                let version = match AnyVersion::from_str(v) {
                     Ok(w) => w,
                     Err(e) => return Err(AnyError(e.to_string())),
-                },
-            )),
+                };
+                if delim == SPEC_DELIM_EXACT {
+                    let is_fully_qualified = match &version {
+                        AnyVersion::Specific(pv) => pv.is_fully_qualified(),
+                        AnyVersion::Latest => false,
+                    };
+                    if is_fully_qualified == false {
+                        return Err(AnyError(format!(
+                            "a version pinned with '{}' must be fully qualified (major.minor.patch)",
+                            SPEC_DELIM_EXACT
+                        )));
+                    }
+                }
+                Ok(Self(name, version, delim))
+            }
             // take entire string as name and refer to latest version
             None => Ok(Self(
                 match PkgPart::from_str(s) {
@@ -893,6 +1015,7 @@ impl FromStr for PartialIpSpec {
                     Err(e) => return Err(AnyError(e.to_string())),
                 },
                 AnyVersion::Latest,
+                SPEC_DELIM.chars().next().unwrap(),
             )),
         }
     }
@@ -900,7 +1023,7 @@ impl FromStr for PartialIpSpec {
 
 impl std::fmt::Display for PartialIpSpec {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}{}", self.get_name(), SPEC_DELIM, self.get_version())
+        write!(f, "{}{}{}", self.get_name(), self.2, self.get_version())
     }
 }
 
@@ -951,4 +1074,73 @@ mod test {
 
         assert_eq!(IpSpec::from_str(&ip).is_err(), true);
     }
+
+    #[test]
+    fn from_str_ip_spec_at_and_hash_delims() {
+        assert_eq!(
+            IpSpec::from_str("name@1.0.0").unwrap(),
+            IpSpec::new(
+                PkgPart::from_str("name").unwrap(),
+                Version::from_str("1.0.0").unwrap()
+            )
+        );
+        assert_eq!(
+            IpSpec::from_str("name#1.0.0").unwrap(),
+            IpSpec::new(
+                PkgPart::from_str("name").unwrap(),
+                Version::from_str("1.0.0").unwrap()
+            )
+        );
+        // an ip spec from an alternate delimiter still equals one from ':'
+        assert_eq!(
+            IpSpec::from_str("name@1.0.0").unwrap(),
+            IpSpec::from_str("name:1.0.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn ip_spec_round_trips_its_own_delimiter() {
+        assert_eq!(
+            IpSpec::from_str("name@1.0.0").unwrap().to_string(),
+            "name@1.0.0"
+        );
+        assert_eq!(
+            IpSpec::from_str("name#1.0.0").unwrap().to_string(),
+            "name#1.0.0"
+        );
+        assert_eq!(
+            IpSpec::from_str("name:1.0.0").unwrap().to_string(),
+            "name:1.0.0"
+        );
+    }
+
+    #[test]
+    fn partial_ip_spec_hash_allows_partial_version() {
+        assert_eq!(
+            PartialIpSpec::from_str("name#1.2").unwrap().to_string(),
+            "name#1.2"
+        );
+        assert_eq!(
+            PartialIpSpec::from_str("name#1").unwrap().to_string(),
+            "name#1"
+        );
+    }
+
+    #[test]
+    fn partial_ip_spec_at_requires_fully_qualified_version() {
+        assert_eq!(PartialIpSpec::from_str("name@1.2").is_err(), true);
+        assert_eq!(PartialIpSpec::from_str("name@1").is_err(), true);
+        assert_eq!(PartialIpSpec::from_str("name@1.2.0").is_err(), false);
+    }
+
+    #[test]
+    fn parse_bare_name_rejects_an_attached_version() {
+        assert_eq!(
+            parse_bare_name("name").unwrap(),
+            PkgPart::from_str("name").unwrap()
+        );
+        assert_eq!(parse_bare_name("name@1.0.0").is_err(), true);
+        assert_eq!(parse_bare_name("name#1.0.0").is_err(), true);
+        assert_eq!(parse_bare_name("name:1.0.0").is_err(), true);
+    }
 }
@@ -1,7 +1,185 @@
-use crate::core::lang::Language;
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
 
-#[derive(Debug, PartialEq)]
+//! Layered name resolution for a [CrossIdentifier]: an unqualified
+//! reference to an HDL design unit that may be satisfied by the current
+//! ip, one of its dependencies, or a dependency of a dependency.
+
+use std::collections::HashMap;
+
+use super::{LangIdentifier, LangUnit, Language};
+use crate::core::pkgid::PkgPart;
+
+/// A reference to an HDL design unit as it appears in a source file.
+///
+/// `raw` names the unit and, through its [LangIdentifier] variant, the HDL
+/// it was written against. `language` is the language configuration of
+/// the file doing the referencing, and only matters when a candidate unit
+/// is written in a *different* HDL than `raw`: see [CrossIdentifier::matches].
+#[derive(Debug, PartialEq, Clone)]
 pub struct CrossIdentifier {
     language: Language,
-    raw: String,
+    raw: LangIdentifier,
+}
+
+impl CrossIdentifier {
+    pub fn new(language: Language, raw: LangIdentifier) -> Self {
+        Self { language, raw }
+    }
+
+    pub fn get_language(&self) -> &Language {
+        &self.language
+    }
+
+    pub fn get_raw(&self) -> &LangIdentifier {
+        &self.raw
+    }
+
+    /// Checks whether `candidate` is a plausible match for this reference.
+    ///
+    /// The names must agree. If `candidate` was written in the same HDL as
+    /// `raw`, that is enough. If it was written in a different HDL, it only
+    /// counts when the consuming file has every involved language enabled,
+    /// i.e. it actually mixes languages rather than happening to share a
+    /// name with something in a language it never touches.
+    fn matches(&self, candidate: &LangIdentifier) -> bool {
+        if self.raw.as_str() != candidate.as_str() {
+            return false;
+        }
+        if std::mem::discriminant(&self.raw) == std::mem::discriminant(candidate) {
+            return true;
+        }
+        let supports = |id: &LangIdentifier| match id {
+            LangIdentifier::Vhdl(_) => self.language.supports_vhdl(),
+            LangIdentifier::Verilog(_) => self.language.supports_verilog(),
+            LangIdentifier::SystemVerilog(_) => self.language.supports_systemverilog(),
+        };
+        supports(&self.raw) && supports(candidate)
+    }
+}
+
+/// Where a [CrossIdentifier] was ultimately found, in precedence order.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Scope {
+    /// Defined by the ip that contains the reference.
+    CurrentIp,
+    /// Exported by an ip the resolver selected as a direct dependency.
+    DirectDependency(PkgPart),
+    /// Reachable only through a dependency of a dependency.
+    TransitiveDependency(PkgPart),
+}
+
+/// The set of design units visible at one of [CrossIdentifier]'s non-local
+/// scopes: every dependency ip the scope reaches, paired with the units it
+/// exports.
+pub type DependencyScope = Vec<(PkgPart, HashMap<LangIdentifier, LangUnit>)>;
+
+#[derive(Debug, PartialEq)]
+pub enum CrossResolveError {
+    /// No scope defines a unit matching the reference.
+    NotFound(LangIdentifier),
+    /// Two or more ips at the same scope export a matching unit; names the
+    /// package each competing definition came from.
+    Ambiguous {
+        name: LangIdentifier,
+        competing: Vec<PkgPart>,
+    },
+}
+
+impl std::error::Error for CrossResolveError {}
+
+impl std::fmt::Display for CrossResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(
+                f,
+                "no design unit named '{}' was found in the current ip or its dependencies",
+                name
+            ),
+            Self::Ambiguous { name, competing } => write!(
+                f,
+                "design unit '{}' is ambiguous: defined by {}",
+                name,
+                competing
+                    .iter()
+                    .map(|p| format!("'{}'", p))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Resolves `id` against three scopes in precedence order: the ip's own
+/// units shadow a direct dependency's, which in turn shadow anything only
+/// reachable transitively. A name ambiguous *within* a single scope (two
+/// dependencies at the same precedence both defining it) is an error
+/// rather than an arbitrary pick; a name that shadows an earlier,
+/// higher-precedence match never reaches this error at all.
+pub fn resolve(
+    id: &CrossIdentifier,
+    own_units: &HashMap<LangIdentifier, LangUnit>,
+    direct: &DependencyScope,
+    transitive: &DependencyScope,
+) -> Result<(Scope, LangIdentifier), CrossResolveError> {
+    if let Some(name) = find_in_own_units(id, own_units) {
+        return Ok((Scope::CurrentIp, name));
+    }
+    if let Some((name, pkg)) = find_in_dependency_scope(id, direct)? {
+        return Ok((Scope::DirectDependency(pkg), name));
+    }
+    if let Some((name, pkg)) = find_in_dependency_scope(id, transitive)? {
+        return Ok((Scope::TransitiveDependency(pkg), name));
+    }
+    Err(CrossResolveError::NotFound(id.get_raw().clone()))
+}
+
+fn find_in_own_units(
+    id: &CrossIdentifier,
+    own_units: &HashMap<LangIdentifier, LangUnit>,
+) -> Option<LangIdentifier> {
+    own_units
+        .keys()
+        .find(|k| id.matches(k))
+        .map(|k| k.clone())
+}
+
+/// Looks for a match for `id` across every ip in `scope`, erroring if more
+/// than one ip in this same scope exports a matching unit.
+fn find_in_dependency_scope(
+    id: &CrossIdentifier,
+    scope: &DependencyScope,
+) -> Result<Option<(LangIdentifier, PkgPart)>, CrossResolveError> {
+    let mut hits: Vec<(LangIdentifier, PkgPart)> = Vec::new();
+    for (pkg, units) in scope {
+        if let Some(k) = units.keys().find(|k| id.matches(k)) {
+            hits.push((k.clone(), pkg.clone()));
+        }
+    }
+    match hits.len() {
+        0 => Ok(None),
+        1 => Ok(Some(hits.remove(0))),
+        _ => {
+            let mut competing: Vec<PkgPart> = hits.into_iter().map(|(_, pkg)| pkg).collect();
+            competing.sort();
+            Err(CrossResolveError::Ambiguous {
+                name: id.get_raw().clone(),
+                competing,
+            })
+        }
+    }
 }
@@ -0,0 +1,109 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::core::lang::LangIdentifier;
+use crate::core::lang::LangUnit;
+use crate::error::Error;
+use crate::util::anyerror::Fault;
+
+/// Orders `units` so every dependency appears before the unit that references
+/// it, starting the depth-first search from `roots`.
+///
+/// References that do not resolve to a unit in `units` are assumed to be
+/// external or primitive and are silently skipped. Units reachable from more
+/// than one root, or from more than one path, are only emitted once.
+pub fn compile_order<'a>(
+    units: &'a HashMap<LangIdentifier, LangUnit>,
+    roots: &[LangIdentifier],
+) -> Result<Vec<&'a LangUnit>, Fault> {
+    let mut gray: HashSet<LangIdentifier> = HashSet::new();
+    let mut black: HashSet<LangIdentifier> = HashSet::new();
+    let mut order: Vec<&LangUnit> = Vec::new();
+
+    for root in roots {
+        let unit = match units.get(root) {
+            Some(u) => u,
+            None => continue,
+        };
+        if black.contains(&unit.get_name()) == false {
+            visit(unit, units, &mut gray, &mut black, &mut order)?;
+        }
+    }
+
+    Ok(order)
+}
+
+/// Performs the post-order DFS visit for [compile_order], tracking `gray`
+/// (in-progress) and `black` (finished) sets to detect back-edges (cycles)
+/// and avoid revisiting a unit that is already placed in `order`.
+fn visit<'a>(
+    unit: &'a LangUnit,
+    units: &'a HashMap<LangIdentifier, LangUnit>,
+    gray: &mut HashSet<LangIdentifier>,
+    black: &mut HashSet<LangIdentifier>,
+    order: &mut Vec<&'a LangUnit>,
+) -> Result<(), Fault> {
+    let name = unit.get_name();
+
+    gray.insert(name.clone());
+
+    for dep_name in unit.get_references() {
+        let dep_unit = match units.get(&dep_name) {
+            Some(u) => u,
+            // an unresolved reference is external/primitive, not a compile-order node
+            None => continue,
+        };
+        let dep_name = dep_unit.get_name();
+        if black.contains(&dep_name) == true {
+            continue;
+        }
+        if gray.contains(&dep_name) == true {
+            return Err(Error::CompileOrderCycle(name, dep_name))?;
+        }
+        visit(dep_unit, units, gray, black, order)?;
+    }
+
+    gray.remove(&name);
+    black.insert(name);
+    order.push(unit);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lang::verilog::token::identifier::Identifier::Basic as VerilogBasic;
+    use crate::core::lang::vhdl::token::identifier::Identifier::Basic as VhdlBasic;
+
+    #[test]
+    fn ut_identifiers_cross_language_hash_eq() {
+        // sanity check the invariant compile_order relies on: a unit declared
+        // in one language can be looked up by a reference tagged with
+        // another language's identifier type.
+        let vhdl_id = LangIdentifier::Vhdl(VhdlBasic("top".to_string()));
+        let verilog_id = LangIdentifier::Verilog(VerilogBasic("top".to_string()));
+        assert_eq!(vhdl_id, verilog_id);
+
+        let mut set = HashSet::new();
+        set.insert(vhdl_id);
+        assert!(set.contains(&verilog_id));
+    }
+}
@@ -30,6 +30,7 @@ pub trait Tokenize {
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token<T> {
     position: Position,
+    end: Position,
     ttype: T,
 }
 
@@ -53,10 +54,41 @@ impl<T> Token<T> {
         &self.position
     }
 
-    /// Creates a new token.
+    /// Returns the position of the last character the token covers.
+    ///
+    /// Equal to [Self::locate] for a token built with [Self::new] (a single
+    /// position, not a span); see [Self::new_spanned] for tokenizers that
+    /// track both ends of a token.
+    pub fn end_position(&self) -> &Position {
+        &self.end
+    }
+
+    /// Bundles [Self::locate] and [Self::end_position] into a single
+    /// [Span], for callers that want the full range a token covers as one
+    /// value instead of two separate accessor calls; e.g.
+    /// [crate::core::lang::vhdl::token::diagnostic::Log] stores one of
+    /// these per lexing problem it reports.
+    pub fn span(&self) -> Span {
+        Span {
+            start: self.position.clone(),
+            end: self.end.clone(),
+        }
+    }
+
+    /// Creates a new token positioned at a single point in the file.
     pub fn new(ttype: T, loc: Position) -> Self {
         Self {
-            position: loc,
+            position: loc.clone(),
+            end: loc,
+            ttype: ttype,
+        }
+    }
+
+    /// Creates a new token spanning from `start` to `end`.
+    pub fn new_spanned(ttype: T, start: Position, end: Position) -> Self {
+        Self {
+            position: start,
+            end: end,
             ttype: ttype,
         }
     }
@@ -97,8 +129,8 @@ impl<T: Display> Display for TokenError<T> {
 use std::cmp::Ordering;
 
 #[derive(Debug, PartialEq, Clone, Ord, Eq)]
-/// (Line, Col)
-pub struct Position(usize, usize);
+/// (Line, Col, absolute byte offset)
+pub struct Position(usize, usize, usize);
 
 impl PartialOrd for Position {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -113,12 +145,16 @@ impl PartialOrd for Position {
 impl Position {
     /// Creates a new `Position` struct as line 1, col 0.
     pub fn new() -> Self {
-        Position(1, 0)
+        Position(1, 0, 0)
     }
 
     /// Creates a `Position` struct at a particular location `line`:`col`.
+    ///
+    /// The byte offset is left at 0 since the two-argument call sites this
+    /// constructor predates (mostly test assertions) have no byte to give;
+    /// use [Self::step] from a real [TrainCar] walk to track it.
     pub fn place(line: usize, col: usize) -> Self {
-        Self(line, col)
+        Self(line, col, 0)
     }
 
     /// Increments the column counter by 1.
@@ -126,10 +162,12 @@ impl Position {
         self.1 += 1;
     }
 
-    /// Increments the column counter by 1. If the current char `c` is a newline,
-    /// it will then drop down to the next line.
+    /// Increments the column counter by 1, advances the byte offset by the
+    /// UTF-8 length of `c`, and if `c` is a newline, drops down to the next
+    /// line.
     pub fn step(&mut self, c: &char) {
         self.next_col();
+        self.2 += c.len_utf8();
         if c == &'\n' {
             self.next_line();
         }
@@ -153,6 +191,11 @@ impl Position {
         self.1
     }
 
+    /// Access the absolute byte offset (`.2`) into the source text.
+    pub fn byte_offset(&self) -> usize {
+        self.2
+    }
+
     /// Appends the position by adding lines and setting column.
     pub fn fast_forward(&mut self, other: &Position) {
         if other.0 > 1 {
@@ -165,6 +208,16 @@ impl Position {
     }
 }
 
+/// The full range of source text a [Token] covers, from where it starts to
+/// where it ends. See [Token::span]; used in place of a raw
+/// `(Position, Position)` pair wherever a span is handed to a caller, e.g.
+/// [crate::core::lang::vhdl::token::diagnostic::Log::span].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
 impl std::fmt::Display for Position {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, ":{}:{}", self.0, self.1)
@@ -220,6 +273,95 @@ where
     }
 }
 
+/// A cursor over the original `&str` source that tracks a byte offset
+/// instead of walking `char`-by-`char`, so a run of token text can be
+/// handed back as a zero-copy `&str` slice of the source instead of being
+/// rebuilt a character at a time into a fresh `String` (compare
+/// [TrainCar]-based callers like `consume_value_pattern`, which do exactly
+/// that rebuilding).
+///
+/// VHDL's delimiters, keywords, and digits are all ASCII, so
+/// [Self::take_while] scans bytes directly; callers that need a non-ASCII
+/// graphic character (inside a string or character literal) fall back to
+/// [Self::bump]/[Self::peek_char], which decode one `char` at the current
+/// position instead of assuming single-byte encoding.
+///
+/// Used by [super::vhdl::token::raw::lex_raw] for a pure-`&str`, span-free
+/// classification pass over VHDL source.
+pub struct ByteCursor<'a> {
+    source: &'a str,
+    pos: usize,
+    loc: Position,
+}
+
+impl<'a> ByteCursor<'a> {
+    /// Creates a new cursor positioned at the start of `source`.
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            pos: 0,
+            loc: Position::new(),
+        }
+    }
+
+    /// Returns the absolute byte offset of the cursor's current position.
+    pub fn byte_offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the line:col position of the cursor.
+    pub fn locate(&self) -> &Position {
+        &self.loc
+    }
+
+    /// Returns the remaining, unconsumed source text as a zero-copy slice.
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    /// Peeks the next byte without consuming it.
+    ///
+    /// `None` past the end of the source. At a multi-byte character this
+    /// returns only its first (non-ASCII) byte; use [Self::peek_char] when a
+    /// full, possibly non-ASCII character is needed.
+    pub fn peek_byte(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    /// Decodes and peeks the next full `char` without consuming it.
+    pub fn peek_char(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Consumes and returns the next `char`, stepping the position marker
+    /// by its real UTF-8 length (not just one byte).
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.pos += c.len_utf8();
+        self.loc.step(&c);
+        Some(c)
+    }
+
+    /// Consumes bytes while `pred` holds for each one, returning the
+    /// consumed run as a zero-copy slice of the original source.
+    ///
+    /// Only sound for ASCII predicates: a byte that is part of a
+    /// multi-byte character is never handed to `pred` (scanning stops
+    /// there instead), so callers needing non-ASCII graphic-character
+    /// handling should use [Self::bump]/[Self::peek_char] instead.
+    pub fn take_while(&mut self, pred: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while let Some(b) = self.peek_byte() {
+            if !b.is_ascii() || !pred(b) {
+                break;
+            }
+            self.pos += 1;
+            self.loc.step(&(b as char));
+        }
+        &self.source[start..self.pos]
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -237,4 +379,34 @@ mod test {
         pos.next_line();
         assert_eq!(pos, Position::place(3, 0));
     }
+
+    #[test]
+    fn byte_cursor_take_while_is_zero_copy() {
+        let source = "entity foo";
+        let mut cur = ByteCursor::new(source);
+        let word = cur.take_while(|b| b.is_ascii_alphabetic());
+        assert_eq!(word, "entity");
+        // the returned slice borrows straight from `source`, no new allocation
+        assert_eq!(word.as_ptr(), source.as_ptr());
+        assert_eq!(cur.byte_offset(), 6);
+
+        cur.take_while(|b| b == b' ');
+        let word = cur.take_while(|b| b.is_ascii_alphabetic());
+        assert_eq!(word, "foo");
+        assert_eq!(cur.byte_offset(), 10);
+        assert_eq!(cur.peek_byte(), None);
+    }
+
+    #[test]
+    fn byte_cursor_bump_handles_utf8() {
+        // "é" is a 2-byte UTF-8 character; the cursor should still advance
+        // by one full char, not one byte
+        let source = "é!";
+        let mut cur = ByteCursor::new(source);
+        assert_eq!(cur.bump(), Some('é'));
+        assert_eq!(cur.byte_offset(), 2);
+        assert_eq!(cur.bump(), Some('!'));
+        assert_eq!(cur.byte_offset(), 3);
+        assert_eq!(cur.bump(), None);
+    }
 }
@@ -26,6 +26,10 @@ pub mod node;
 
 pub mod reference;
 
+pub mod cross;
+pub mod hdl;
+pub mod script;
+
 use crate::error::Error;
 use crate::error::Hint;
 use crate::util::anyerror::AnyError;
@@ -36,6 +40,7 @@ use lexer::Position;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::path::PathBuf;
@@ -59,13 +64,24 @@ pub fn read_to_string(source_file: &str) -> Result<String, Fault> {
     let contents = match std::fs::read_to_string(&source_file) {
         Ok(dump) => dump,
         Err(e) => {
-            // try to return a string from utf-16
+            // not valid utf-8 as-is; sniff the raw bytes for a byte-order-mark
+            // before giving up on producing meaningful text
             if e.kind() == std::io::ErrorKind::InvalidData {
-                String::from_utf8_lossy(&match std::fs::read(&source_file) {
+                let bytes = match std::fs::read(&source_file) {
                     Ok(r) => r,
                     Err(e) => return Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
-                })
-                .into_owned()
+                };
+                if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+                    // utf-8 bom: strip it and decode the remainder as normal
+                    String::from_utf8_lossy(&bytes[3..]).into_owned()
+                } else if bytes.starts_with(&[0xFF, 0xFE]) {
+                    decode_utf16(&bytes[2..], u16::from_le_bytes, source_file)?
+                } else if bytes.starts_with(&[0xFE, 0xFF]) {
+                    decode_utf16(&bytes[2..], u16::from_be_bytes, source_file)?
+                } else {
+                    // no declared encoding: fall back to a lossy utf-8 read
+                    String::from_utf8_lossy(&bytes).into_owned()
+                }
             } else {
                 return Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?;
             }
@@ -74,6 +90,24 @@ pub fn read_to_string(source_file: &str) -> Result<String, Fault> {
     Ok(contents)
 }
 
+/// Reconstructs `u16` code units from `bytes` using `from_bytes` (little- or
+/// big-endian) and decodes them as UTF-16, naming `source_file` in the
+/// returned [Fault] if the declared encoding cannot be decoded.
+fn decode_utf16(
+    bytes: &[u8],
+    from_bytes: fn([u8; 2]) -> u16,
+    source_file: &str,
+) -> Result<String, Fault> {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+    match String::from_utf16(&units) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
+    }
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Language {
@@ -184,12 +218,14 @@ pub trait Code {
 #[derive(Debug, PartialEq)]
 pub struct SharedData {
     visibility: Visibility,
+    targets: HashSet<String>,
 }
 
 impl SharedData {
     pub fn new() -> Self {
         Self {
             visibility: Visibility::default(),
+            targets: HashSet::new(),
         }
     }
 
@@ -200,6 +236,28 @@ impl SharedData {
     pub fn get_visibility(&self) -> &Visibility {
         &self.visibility
     }
+
+    pub fn set_targets(&mut self, targets: HashSet<String>) {
+        self.targets = targets;
+    }
+
+    pub fn get_targets(&self) -> &HashSet<String> {
+        &self.targets
+    }
+
+    /// Checks if this data is relevant to any of the given `targets`.
+    ///
+    /// An empty `targets` filter means no target scoping was requested, so
+    /// everything matches. Otherwise, data with no tags of its own applies to
+    /// every target, and tagged data matches if it shares at least one tag
+    /// with `targets`.
+    pub fn matches_targets(&self, targets: &HashSet<String>) -> bool {
+        if targets.is_empty() || self.targets.is_empty() {
+            true
+        } else {
+            self.targets.iter().any(|t| targets.contains(t))
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -247,6 +305,32 @@ impl LangUnit {
         };
     }
 
+    pub fn get_targets(&self) -> &HashSet<String> {
+        match &self {
+            Self::Vhdl(_, sd) => sd.get_targets(),
+            Self::Verilog(_, sd) => sd.get_targets(),
+            Self::SystemVerilog(_, sd) => sd.get_targets(),
+        }
+    }
+
+    pub fn set_targets(&mut self, targets: HashSet<String>) {
+        match self {
+            Self::Vhdl(_, sd) => sd.set_targets(targets),
+            Self::Verilog(_, sd) => sd.set_targets(targets),
+            Self::SystemVerilog(_, sd) => sd.set_targets(targets),
+        };
+    }
+
+    /// Checks if this unit is relevant to any of the given `targets` (see
+    /// [SharedData::matches_targets]).
+    pub fn matches_targets(&self, targets: &HashSet<String>) -> bool {
+        match &self {
+            Self::Vhdl(_, sd) => sd.matches_targets(targets),
+            Self::Verilog(_, sd) => sd.matches_targets(targets),
+            Self::SystemVerilog(_, sd) => sd.matches_targets(targets),
+        }
+    }
+
     /// References the unit's identifier.
     pub fn get_name(&self) -> LangIdentifier {
         match &self {
@@ -360,22 +444,40 @@ impl LangUnit {
             "type",
             toml_edit::value(&self.to_string()).into_value().unwrap(),
         );
+        tbl.insert(
+            "visibility",
+            toml_edit::value(&self.get_visibility().to_string())
+                .into_value()
+                .unwrap(),
+        );
         item
     }
 
     /// Deserializes the data from a toml inline table.
+    ///
+    /// Returns `None` if `tbl` names an unrecognized language or is otherwise
+    /// malformed, so a corrupt catalog entry is dropped rather than panicking.
     pub fn from_toml(tbl: &toml_edit::InlineTable) -> Option<Self> {
         let entry = tbl.get("language")?.as_str()?;
+
+        let mut shared = SharedData::new();
+        if let Some(v) = tbl.get("visibility").and_then(|v| v.as_str()) {
+            shared.set_visibility(match v {
+                "public" => Visibility::Public,
+                "protected" => Visibility::Protected,
+                "private" => Visibility::Private,
+                _ => Visibility::default(),
+            });
+        }
+
         match entry {
-            "vhdl" => Some(Self::Vhdl(
-                VhdlPrimaryUnit::from_toml(tbl)?,
-                SharedData::new(),
+            "vhdl" => Some(Self::Vhdl(VhdlPrimaryUnit::from_toml(tbl)?, shared)),
+            "verilog" => Some(Self::Verilog(VerilogPrimaryUnit::from_toml(tbl)?, shared)),
+            "systemverilog" => Some(Self::SystemVerilog(
+                SystemVerilogPrimaryUnit::from_toml(tbl)?,
+                shared,
             )),
-            "verilog" => Some(Self::Verilog(
-                VerilogPrimaryUnit::from_toml(tbl)?,
-                SharedData::new(),
-            )),
-            _ => panic!("unknown entry in serialized toml table {}", entry),
+            _ => None,
         }
     }
 }
@@ -488,9 +590,16 @@ impl Display for LangIdentifier {
     }
 }
 
+/// Gathers the primary design units found across `files`.
+///
+/// When `targets` is non-empty, only units tagged for one of those targets
+/// (or carrying no target tags at all, meaning they apply everywhere — see
+/// [SharedData::matches_targets]) are kept, mirroring how a `--target` flag
+/// narrows the emitted source group for a single tool/flow invocation.
 pub fn collect_units(
     files: &Vec<String>,
     lang_mode: &Language,
+    targets: &HashSet<String>,
 ) -> Result<HashMap<LangIdentifier, LangUnit>, Box<dyn std::error::Error>> {
     // collect the VHDL units
     let vhdl_units = match lang_mode.supports_vhdl() {
@@ -572,5 +681,34 @@ pub fn collect_units(
             ))?;
         }
     }
+
+    // narrow the result down to the requested targets, if any were given
+    if targets.is_empty() == false {
+        results.retain(|_, v| v.matches_targets(targets));
+    }
+
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ut_shared_data_matches_targets() {
+        let mut sd = SharedData::new();
+        // no scoping requested: always matches
+        assert_eq!(sd.matches_targets(&HashSet::new()), true);
+
+        // untagged data applies to every requested target
+        let synth: HashSet<String> = vec![String::from("synthesis")].into_iter().collect();
+        assert_eq!(sd.matches_targets(&synth), true);
+
+        // tagged data only matches a requested target it shares
+        sd.set_targets(vec![String::from("simulation")].into_iter().collect());
+        assert_eq!(sd.matches_targets(&synth), false);
+
+        let sim: HashSet<String> = vec![String::from("simulation")].into_iter().collect();
+        assert_eq!(sd.matches_targets(&sim), true);
+    }
+}
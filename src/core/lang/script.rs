@@ -0,0 +1,123 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::core::lang::hdl;
+use crate::core::lang::Lang;
+use crate::core::lang::LangIdentifier;
+use crate::core::lang::LangUnit;
+use crate::core::visibility::Visibility;
+use crate::util::anyerror::AnyError;
+use crate::util::anyerror::Fault;
+
+/// The tool-script formats [write_script] is able to emit from a collected
+/// unit set (see [super::collect_units]).
+#[derive(Debug, PartialEq)]
+pub enum ScriptFormat {
+    /// A plain list of source files, one per line, in compile order.
+    FileList,
+    /// A Questa/ModelSim `.do`-style compile script.
+    Modelsim,
+}
+
+impl Default for ScriptFormat {
+    fn default() -> Self {
+        Self::FileList
+    }
+}
+
+impl FromStr for ScriptFormat {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_ref() {
+            "flist" => Ok(Self::FileList),
+            "modelsim" => Ok(Self::Modelsim),
+            _ => Err(AnyError(format!("unsupported script format: {:?}", s))),
+        }
+    }
+}
+
+/// Renders a single `unit` as one line of the requested `format`.
+fn write_line(unit: &LangUnit, format: &ScriptFormat) -> String {
+    match format {
+        ScriptFormat::FileList => unit.get_source_file().to_string(),
+        ScriptFormat::Modelsim => {
+            let cmd = match unit.get_lang() {
+                Lang::Vhdl => "vcom",
+                Lang::Verilog | Lang::SystemVerilog => "vlog",
+            };
+            format!("{} {}", cmd, unit.get_source_file())
+        }
+    }
+}
+
+/// Builds a tool script in the requested `format` from the units produced by
+/// [super::collect_units], ordered by [hdl::compile_order] (rooted at every
+/// [Visibility::Public] unit) so a downstream tool can compile the emitted
+/// script top-to-bottom without resolving dependencies itself.
+///
+/// Units whose [Visibility] is not [Visibility::Public] are left out when
+/// `public_only` is set, units that don't match `targets` are left out (see
+/// [LangUnit::matches_targets]), and each source file is only listed once
+/// even if it declares more than one design element.
+pub fn write_script(
+    units: &HashMap<LangIdentifier, LangUnit>,
+    format: &ScriptFormat,
+    public_only: bool,
+    targets: &HashSet<String>,
+) -> Result<String, Fault> {
+    let roots: Vec<LangIdentifier> = units
+        .values()
+        .filter(|u| u.get_visibility() == &Visibility::Public)
+        .map(|u| u.get_name())
+        .collect();
+    let ordered = hdl::compile_order(units, &roots)?;
+
+    let mut seen_files: HashSet<&str> = HashSet::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    for unit in ordered {
+        if public_only == true && unit.get_visibility() != &Visibility::Public {
+            continue;
+        }
+        if unit.matches_targets(targets) == false {
+            continue;
+        }
+        if seen_files.insert(unit.get_source_file()) == false {
+            continue;
+        }
+        lines.push(write_line(unit, format));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ut_script_format_from_str() {
+        assert_eq!(ScriptFormat::from_str("flist").unwrap(), ScriptFormat::FileList);
+        assert_eq!(ScriptFormat::from_str("MODELSIM").unwrap(), ScriptFormat::Modelsim);
+        assert!(ScriptFormat::from_str("bogus").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::fmt;
+
+use super::token::{keyword::Keyword, operator::Operator};
+use crate::core::lang::lexer::Position;
+use crate::core::lang::verilog::error::VerilogError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SystemVerilogError {
+    #[error("invalid syntax")]
+    Vague(Position),
+    #[error("expecting \"{0}\" keyword")]
+    ExpectingKeyword(Keyword, Position),
+    #[error("expecting \"{0}\" delimiter")]
+    ExpectingOperator(Operator, Position),
+    // raised while matching raw characters, before a `Position` is assigned to
+    // the would-be token; the tokenizer pairs this back up with a `Position`
+    // through `lexer::TokenError` at the call site.
+    #[error("invalid sequence {0}")]
+    InvalidSequence(String),
+}
+
+impl SystemVerilogError {
+    /// Returns the position of the token that triggered this error, if one
+    /// was attached when the error was raised.
+    pub fn position(&self) -> Option<&Position> {
+        match self {
+            Self::Vague(pos) => Some(pos),
+            Self::ExpectingKeyword(_, pos) => Some(pos),
+            Self::ExpectingOperator(_, pos) => Some(pos),
+            Self::InvalidSequence(_) => None,
+        }
+    }
+
+    /// Renders the error against the original `source` it was parsed from,
+    /// reconstructing the offending line and underlining the column with a
+    /// caret, in the style of `error: <msg>\n  --> <file><pos>`.
+    pub fn render(&self, source: &str, file: &str) -> String {
+        let pos = match self.position() {
+            Some(pos) => pos,
+            None => return format!("error: {}\n --> {}", self, file),
+        };
+        let line_str = source
+            .lines()
+            .nth(pos.line().saturating_sub(1))
+            .unwrap_or("");
+        let line_no = pos.line().to_string();
+        let margin = " ".repeat(line_no.len());
+        // columns are 1-based, so column 1 needs no leading space
+        let caret = " ".repeat(pos.col().saturating_sub(1));
+        format!(
+            "error: {}\n{} --> {}{}\n{} |\n{} | {}\n{} | {}^",
+            self, margin, file, pos, margin, line_no, line_str, margin, caret,
+        )
+    }
+}
+
+impl From<VerilogError> for SystemVerilogError {
+    /// Lets SystemVerilog symbols reuse the shared Verilog statement/module
+    /// grammar (see `VerilogSymbol`) while still reporting through
+    /// [SystemVerilogError]. The position is unset at this boundary, since
+    /// [VerilogError] does not carry one itself.
+    fn from(value: VerilogError) -> Self {
+        match value {
+            VerilogError::Vague => Self::Vague(Position::new()),
+            VerilogError::ExpectingKeyword(k) => Self::ExpectingKeyword(k, Position::new()),
+            VerilogError::ExpectingOperator(o) => Self::ExpectingOperator(o, Position::new()),
+            VerilogError::InvalidSequence(s) => Self::InvalidSequence(s),
+            _ => Self::Vague(Position::new()),
+        }
+    }
+}
+
+/// A collection of every [SystemVerilogError] encountered while parsing a
+/// source file.
+///
+/// Allows a user with several malformed statements to see all of them with
+/// pinpointed locations in a single pass, rather than fixing and re-running
+/// one error at a time. See [crate::core::lang::sv::symbols::SystemVerilogParser::read].
+#[derive(Debug, PartialEq)]
+pub struct SystemVerilogErrors(pub Vec<SystemVerilogError>);
+
+impl SystemVerilogErrors {
+    /// Renders every contained error against `source`, joining them with a
+    /// blank line between each.
+    pub fn render(&self, source: &str, file: &str) -> String {
+        self.0
+            .iter()
+            .map(|e| e.render(source, file))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+impl fmt::Display for SystemVerilogErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msgs: Vec<String> = self.0.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", msgs.join("\n"))
+    }
+}
+
+impl std::error::Error for SystemVerilogErrors {}
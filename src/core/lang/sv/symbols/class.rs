@@ -87,7 +87,7 @@ impl Class {
         // take the class name
         let name = match tokens.next().take().unwrap().take() {
             SystemVerilogToken::Identifier(id) => id,
-            _ => return Err(SystemVerilogError::Vague),
+            _ => return Err(SystemVerilogError::Vague(pos.clone())),
         };
 
         // initialize container for references to other design elements
@@ -118,7 +118,7 @@ impl Class {
                 let _ = tokens.next().unwrap();
                 let ext_class_name = match tokens.next().take().unwrap().take() {
                     SystemVerilogToken::Identifier(id) => id,
-                    _ => return Err(SystemVerilogError::Vague),
+                    _ => return Err(SystemVerilogError::Vague(pos.clone())),
                 };
                 // println!("extends {}", impl_class_name);
                 refs.insert(CompoundIdentifier::new_minimal_verilog(ext_class_name));
@@ -129,7 +129,7 @@ impl Class {
                             let _ = tokens.next().unwrap();
                             let ext_class_name = match tokens.next().take().unwrap().take() {
                                 SystemVerilogToken::Identifier(id) => id,
-                                _ => return Err(SystemVerilogError::Vague),
+                                _ => return Err(SystemVerilogError::Vague(pos.clone())),
                             };
                             // println!("extends {}", impl_class_name);
                             refs.insert(CompoundIdentifier::new_minimal_verilog(ext_class_name));
@@ -157,7 +157,7 @@ impl Class {
                 let _ = tokens.next().unwrap();
                 let impl_class_name = match tokens.next().take().unwrap().take() {
                     SystemVerilogToken::Identifier(id) => id,
-                    _ => return Err(SystemVerilogError::Vague),
+                    _ => return Err(SystemVerilogError::Vague(pos.clone())),
                 };
                 // println!("implements {}", impl_class_name);
                 refs.insert(CompoundIdentifier::new_minimal_verilog(impl_class_name));
@@ -168,7 +168,7 @@ impl Class {
                             let _ = tokens.next().unwrap();
                             let impl_class_name = match tokens.next().take().unwrap().take() {
                                 SystemVerilogToken::Identifier(id) => id,
-                                _ => return Err(SystemVerilogError::Vague),
+                                _ => return Err(SystemVerilogError::Vague(pos.clone())),
                             };
                             // println!("implements {}", impl_class_name);
                             refs.insert(CompoundIdentifier::new_minimal_verilog(impl_class_name));
@@ -185,7 +185,10 @@ impl Class {
         // take the terminator
         let t = tokens.next().take().unwrap();
         if t.as_type().check_delimiter(&Operator::Terminator) == false {
-            return Err(SystemVerilogError::ExpectingOperator(Operator::Terminator))
+            return Err(SystemVerilogError::ExpectingOperator(
+                Operator::Terminator,
+                t.locate().clone(),
+            ));
         }
 
         // take the class body
@@ -70,7 +70,7 @@ impl Interface {
         // take interface name
         let interface_name = match tokens.next().take().unwrap().take() {
             SystemVerilogToken::Identifier(id) => id,
-            _ => return Err(SystemVerilogError::Vague),
+            _ => return Err(SystemVerilogError::Vague(pos.clone())),
         };
 
         // initialize container for references to other design elements
@@ -96,7 +96,10 @@ impl Interface {
         // parse until finding the ending keyword
         while let Some(t) = tokens.next() {
             if t.as_type().is_eof() == true {
-                return Err(SystemVerilogError::ExpectingKeyword(Keyword::Endinterface));
+                return Err(SystemVerilogError::ExpectingKeyword(
+                    Keyword::Endinterface,
+                    t.locate().clone(),
+                ));
             } else if t.as_type().check_keyword(&Keyword::Endinterface) {
                 // exit the loop for parsing this design element
                 break;
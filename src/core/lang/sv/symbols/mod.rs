@@ -24,8 +24,9 @@ use interface::Interface;
 use module::Module;
 use package::Package;
 use primitive::Primitive;
+use program::Program;
 
-use super::error::SystemVerilogError;
+use super::error::{SystemVerilogError, SystemVerilogErrors};
 use super::token::identifier::Identifier;
 use super::token::operator::Operator;
 use super::token::tokenizer::SystemVerilogTokenizer;
@@ -35,6 +36,7 @@ use crate::core::lang::reference::{CompoundIdentifier, RefSet};
 use crate::core::lang::sv::token::keyword::Keyword;
 use crate::core::lang::sv::token::token::SystemVerilogToken;
 use crate::core::lang::verilog::symbols::VerilogSymbol;
+use crate::util::graphmap::GraphMap;
 use std::str::FromStr;
 
 pub type Statement = Vec<Token<SystemVerilogToken>>;
@@ -70,7 +72,7 @@ pub enum SystemVerilogSymbol {
     Class(Class),
     Primitive(Primitive),
     Checker(Checker),
-    // Program(Program),
+    Program(Program),
 }
 
 impl SystemVerilogSymbol {
@@ -83,6 +85,7 @@ impl SystemVerilogSymbol {
             Self::Class(c) => Some(c.get_name()),
             Self::Primitive(p) => Some(p.get_name()),
             Self::Checker(c) => Some(c.get_name()),
+            Self::Program(p) => Some(p.get_name()),
         }
     }
 
@@ -95,6 +98,7 @@ impl SystemVerilogSymbol {
             Self::Class(c) => c.get_position(),
             Self::Primitive(p) => p.get_position(),
             Self::Checker(c) => c.get_position(),
+            Self::Program(p) => p.get_position(),
         }
     }
 
@@ -114,6 +118,7 @@ impl SystemVerilogSymbol {
             Self::Class(c) => c.get_refs(),
             Self::Primitive(p) => p.get_refs(),
             Self::Checker(c) => c.get_refs(),
+            Self::Program(p) => p.get_refs(),
         }
     }
 
@@ -126,6 +131,7 @@ impl SystemVerilogSymbol {
             Self::Class(c) => c.extend_refs(refs),
             Self::Primitive(p) => p.extend_refs(refs),
             Self::Checker(c) => c.extend_refs(refs),
+            Self::Program(p) => p.extend_refs(refs),
         }
     }
 }
@@ -148,18 +154,29 @@ impl SystemVerilogParser {
         }
     }
 
-    /// Reports an error if one is discovered in the list of symbols or in the tokenizing.
-    pub fn read(s: &str) -> Result<Self, SystemVerilogError> {
+    /// Reports every error discovered in the list of symbols, rather than
+    /// stopping at the first, so a user with several malformed statements
+    /// sees all of them with pinpointed locations (see
+    /// [SystemVerilogError::render]).
+    pub fn read(s: &str) -> Result<Self, SystemVerilogErrors> {
         let symbols = SystemVerilogParser::parse(
-            SystemVerilogTokenizer::from_str(&s)?
+            SystemVerilogTokenizer::from_str(&s)
+                .unwrap()
                 .into_tokens()
                 .into_iter()
                 .filter(|s| s.as_type().is_comment() == false)
                 .collect(),
         );
-        let result: Result<Vec<Symbol<SystemVerilogSymbol>>, SystemVerilogError> =
-            symbols.into_iter().collect();
-        Ok(Self { symbols: result? })
+
+        let (symbols, errors): (Vec<_>, Vec<_>) = symbols.into_iter().partition(|s| s.is_ok());
+        if errors.is_empty() == false {
+            return Err(SystemVerilogErrors(
+                errors.into_iter().map(|e| e.unwrap_err()).collect(),
+            ));
+        }
+        Ok(Self {
+            symbols: symbols.into_iter().map(|s| s.unwrap()).collect(),
+        })
     }
 
     pub fn into_symbols(self) -> Vec<SystemVerilogSymbol> {
@@ -167,6 +184,263 @@ impl SystemVerilogParser {
     }
 }
 
+/// Builds a Graphviz DOT document of the elaboration hierarchy from a list of
+/// parsed design elements.
+///
+/// Each design element (see [SystemVerilogSymbol::as_name]) becomes a node, and
+/// an edge `"parent" -> "child";` is emitted for every sub-module instantiation
+/// ([module::Module::get_edge_list_entities]) plus every package/class reference
+/// surfaced by [SystemVerilogSymbol::get_refs]. Repeated edges are dropped and
+/// identifiers containing characters other than letters, digits, and
+/// underscores are quoted.
+pub fn into_dot_graph(symbols: &[SystemVerilogSymbol]) -> String {
+    let mut seen: std::collections::HashSet<(String, String)> = std::collections::HashSet::new();
+    let mut edges: Vec<(String, String)> = Vec::new();
+    let mut nodes: Vec<String> = Vec::new();
+
+    for sym in symbols {
+        let parent = match sym.as_name() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        if nodes.contains(&parent) == false {
+            nodes.push(parent.clone());
+        }
+
+        let mut children: Vec<String> =
+            sym.get_refs().iter().map(|c| c.to_string()).collect();
+        if let Some(m) = sym.as_module() {
+            children.extend(m.get_edge_list_entities().into_iter().map(|c| c.to_string()));
+        }
+
+        for child in children {
+            let edge = (parent.clone(), child);
+            if seen.insert(edge.clone()) {
+                edges.push(edge);
+            }
+        }
+    }
+
+    let mut dot = String::from("digraph {\n");
+    for node in nodes {
+        dot.push_str(&format!("    {};\n", quote_dot_id(&node)));
+    }
+    for (parent, child) in edges {
+        dot.push_str(&format!(
+            "    {} -> {};\n",
+            quote_dot_id(&parent),
+            quote_dot_id(&child)
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Quotes a Graphviz node id if it contains any character other than a
+/// letter, digit, or underscore, escaping embedded quotes and backslashes.
+fn quote_dot_id(id: &str) -> String {
+    let needs_quotes =
+        id.is_empty() || id.chars().any(|c| c.is_ascii_alphanumeric() == false && c != '_');
+    match needs_quotes {
+        true => format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\"")),
+        false => id.to_string(),
+    }
+}
+
+/// A diagnostic produced by [analyze].
+#[derive(Debug, PartialEq)]
+pub enum AnalyzerError {
+    /// A design element is declared but never referenced anywhere else in the
+    /// symbol set.
+    UnusedElement(Identifier, Position),
+}
+
+impl std::fmt::Display for AnalyzerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnusedElement(name, pos) => {
+                write!(f, "{}warning: unused design element '{}'", pos, name)
+            }
+        }
+    }
+}
+
+/// Runs a lightweight lint pass over a parsed symbol set, reporting design
+/// elements that are declared but never instantiated, imported, or otherwise
+/// referenced.
+///
+/// The symbol table currently records only a design element's name,
+/// declaration [Position], and the [RefSet] of identifiers it references — it
+/// does not yet capture the per-signal net/port declarations inside a
+/// `Module`/`Interface`/`Package`, so the finer-grained diagnostics called out
+/// alongside this one (unused signals, undriven nets, multiply-driven nets)
+/// are left for a future pass once that declaration-level data is captured
+/// during parsing. Names beginning with `_` are exempt from this check, by
+/// convention, the same as an unused signal would be.
+pub fn analyze(symbols: &[SystemVerilogSymbol]) -> Vec<AnalyzerError> {
+    let mut used: RefSet = RefSet::new();
+    for sym in symbols {
+        used.extend(sym.get_refs().iter().cloned());
+        if let Some(m) = sym.as_module() {
+            used.extend(m.get_edge_list_entities());
+        }
+    }
+
+    symbols
+        .iter()
+        .filter_map(|sym| {
+            let name = sym.as_name()?;
+            if name.to_string().starts_with('_') {
+                return None;
+            }
+            let is_used = used
+                .iter()
+                .any(|r| r.get_suffix().to_string() == name.to_string());
+            match is_used {
+                true => None,
+                false => Some(AnalyzerError::UnusedElement(
+                    name.clone(),
+                    sym.get_position().clone(),
+                )),
+            }
+        })
+        .collect()
+}
+
+/// The resolved elaboration hierarchy produced by [elaborate].
+///
+/// Nodes are design elements keyed by [SystemVerilogSymbol::as_name]; edges
+/// point from a dependency to whatever depends on it (the same direction
+/// [crate::commands::plan] wires up when connecting a build's dependency
+/// graph), so [Elaboration::compilation_order] yields dependencies before
+/// their dependents.
+pub struct Elaboration<'a> {
+    graph: GraphMap<String, &'a SystemVerilogSymbol, ()>,
+    unresolved: Vec<CompoundIdentifier>,
+}
+
+impl<'a> Elaboration<'a> {
+    /// Returns the design elements a node with the given `name` depends on.
+    pub fn get_dependencies(&self, name: &str) -> Vec<&str> {
+        let index = match self.graph.get_node_by_key(&name.to_string()) {
+            Some(n) => n.index(),
+            None => return Vec::new(),
+        };
+        self.graph
+            .get_graph()
+            .predecessors(index)
+            .map(|i| self.graph.get_key_by_index(i).unwrap().as_str())
+            .collect()
+    }
+
+    /// Returns every reference that did not resolve to a known design
+    /// element while building the graph.
+    pub fn get_unresolved(&self) -> &[CompoundIdentifier] {
+        &self.unresolved
+    }
+
+    /// Runs a depth-first search over the graph to find a circular
+    /// dependency, returning the cycle as the ordered list of node names
+    /// that participate in it.
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        fn visit(
+            graph: &crate::util::graph::Graph<String, ()>,
+            node: usize,
+            state: &mut Vec<u8>,
+            path: &mut Vec<usize>,
+        ) -> Option<Vec<usize>> {
+            state[node] = 1;
+            path.push(node);
+            for succ in graph.successors(node) {
+                match state[succ] {
+                    1 => {
+                        let start = path.iter().position(|i| i == &succ).unwrap();
+                        return Some(path[start..].to_vec());
+                    }
+                    0 => {
+                        if let Some(cycle) = visit(graph, succ, state, path) {
+                            return Some(cycle);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            path.pop();
+            state[node] = 2;
+            None
+        }
+
+        let graph = self.graph.get_graph();
+        let mut state = vec![0u8; graph.node_count()];
+        for i in 0..graph.node_count() {
+            if state[i] == 0 {
+                if let Some(cycle) = visit(graph, i, &mut state, &mut Vec::new()) {
+                    return Some(
+                        cycle
+                            .into_iter()
+                            .map(|i| self.graph.get_key_by_index(i).unwrap().clone())
+                            .collect(),
+                    );
+                }
+            }
+        }
+        None
+    }
+
+    /// Exposes a topological ordering of the resolved nodes, dependencies
+    /// before dependents, suitable for handing a compilation order to a
+    /// downstream simulator or synthesizer.
+    pub fn compilation_order(&self) -> Vec<&str> {
+        self.graph
+            .get_graph()
+            .topological_sort()
+            .into_iter()
+            .map(|i| self.graph.get_key_by_index(i).unwrap().as_str())
+            .collect()
+    }
+}
+
+/// Resolves every design element's [SystemVerilogSymbol::get_refs] (and, for
+/// modules, [module::Module::get_edge_list_entities]) against the other
+/// elements in `symbols`, linking them into a directed elaboration graph.
+///
+/// References that do not resolve to a known design element are reported
+/// through [Elaboration::get_unresolved] rather than silently dropped or
+/// treated as edges. The graph may still contain a circular dependency; call
+/// [Elaboration::find_cycle] before relying on [Elaboration::compilation_order].
+pub fn elaborate<'a>(symbols: &'a [SystemVerilogSymbol]) -> Elaboration<'a> {
+    let mut graph: GraphMap<String, &SystemVerilogSymbol, ()> = GraphMap::new();
+    for sym in symbols {
+        if let Some(name) = sym.as_name() {
+            graph.add_node(name.to_string(), sym);
+        }
+    }
+
+    let mut unresolved = Vec::new();
+    for sym in symbols {
+        let name = match sym.as_name() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let mut deps: Vec<CompoundIdentifier> = sym.get_refs().iter().cloned().collect();
+        if let Some(m) = sym.as_module() {
+            deps.extend(m.get_edge_list_entities());
+        }
+
+        for dep in deps {
+            let dep_key = dep.get_suffix().to_string();
+            if graph.has_node_by_key(&dep_key) {
+                graph.add_edge_by_key(&dep_key, &name, ());
+            } else {
+                unresolved.push(dep);
+            }
+        }
+    }
+
+    Elaboration { graph, unresolved }
+}
+
 impl Parse<SystemVerilogToken> for SystemVerilogParser {
     type SymbolType = SystemVerilogSymbol;
     type SymbolError = SystemVerilogError;
@@ -252,6 +526,14 @@ impl Parse<SystemVerilogToken> for SystemVerilogParser {
                         Err(e) => Err(e),
                     },
                 )
+            // create program design element
+            } else if t.as_type().check_keyword(&Keyword::Program) {
+                symbols.push(
+                    match SystemVerilogSymbol::parse_program(&mut tokens, t.into_position()) {
+                        Ok(prog) => Ok(Symbol::new(prog)),
+                        Err(e) => Err(e),
+                    },
+                )
             // take a global import statement
             } else if t.as_type().check_keyword(&Keyword::Import) {
                 // verify the import statement parsed okay
@@ -266,6 +548,20 @@ impl Parse<SystemVerilogToken> for SystemVerilogParser {
                 if let Some(i_refs) = i_refs {
                     global_refs.extend(i_refs);
                 }
+            // take a global bind directive and attribute it as references
+            } else if t.as_type().check_keyword(&Keyword::Bind) {
+                // verify the bind statement parsed okay
+                let b_refs = match SystemVerilogSymbol::parse_bind_statement(&mut tokens) {
+                    Ok(b) => Some(b),
+                    Err(e) => {
+                        symbols.push(Err(e));
+                        None
+                    }
+                };
+                // append to this file's global references
+                if let Some(b_refs) = b_refs {
+                    global_refs.extend(b_refs);
+                }
             // take attribute and ignore if okay
             } else if t.as_type().check_delimiter(&Operator::AttrL) {
                 match SystemVerilogSymbol::parse_attr(&mut tokens, t.into_position()) {
@@ -358,6 +654,13 @@ impl SystemVerilogSymbol {
         Ok(Self::Primitive(Primitive::from_tokens(tokens, pos)?))
     }
 
+    fn parse_program<I>(tokens: &mut Peekable<I>, pos: Position) -> Result<Self, SystemVerilogError>
+    where
+        I: Iterator<Item = Token<SystemVerilogToken>>,
+    {
+        Ok(Self::Program(Program::from_tokens(tokens, pos)?))
+    }
+
     fn parse_interface<I>(
         tokens: &mut Peekable<I>,
         pos: Position,
@@ -391,7 +694,10 @@ impl SystemVerilogSymbol {
                 break;
             } else if t.as_ref().is_eof() == true {
                 // expecting closing attribute operator
-                return Err(SystemVerilogError::ExpectingOperator(Operator::AttrR));
+                return Err(SystemVerilogError::ExpectingOperator(
+                    Operator::AttrR,
+                    t.locate().clone(),
+                ));
             }
             stmt.push(t);
         }
@@ -411,7 +717,10 @@ impl SystemVerilogSymbol {
         while let Some(t) = tokens.next() {
             // whoops... this shouldn't be the end of the file!
             if t.as_type().is_eof() {
-                return Err(SystemVerilogError::ExpectingOperator(Operator::Terminator));
+                return Err(SystemVerilogError::ExpectingOperator(
+                    Operator::Terminator,
+                    t.locate().clone(),
+                ));
             // insert the package identifier!
             } else if is_start_of_item && t.as_type().as_identifier().is_some() {
                 refs.insert(CompoundIdentifier::new_minimal_verilog(
@@ -430,6 +739,42 @@ impl SystemVerilogSymbol {
         Ok(refs)
     }
 
+    /// Parses a `bind` directive, which attaches an instance of another
+    /// module/interface/program into the named target scope.
+    ///
+    /// This function assumes the last token consumed was the `bind` keyword.
+    /// The last token this function will consume is the `;` operator. Only
+    /// the target scope and the bound instance's type name (the first two
+    /// identifiers in the statement) are recorded as references; the
+    /// instance name and any port connections are skipped over.
+    pub fn parse_bind_statement<I>(tokens: &mut Peekable<I>) -> Result<RefSet, SystemVerilogError>
+    where
+        I: Iterator<Item = Token<SystemVerilogToken>>,
+    {
+        let mut refs = RefSet::new();
+        let mut idents_taken = 0;
+        while let Some(t) = tokens.next() {
+            // whoops... this shouldn't be the end of the file!
+            if t.as_type().is_eof() {
+                return Err(SystemVerilogError::ExpectingOperator(
+                    Operator::Terminator,
+                    t.locate().clone(),
+                ));
+            // insert the target scope and the bound instance's type name
+            } else if idents_taken < 2 && t.as_type().as_identifier().is_some() {
+                refs.insert(CompoundIdentifier::new_minimal_verilog(
+                    t.take().take_identifier().unwrap(),
+                ));
+                idents_taken += 1;
+            // stop parsing tokens
+            } else if t.as_type().check_delimiter(&Operator::Terminator) {
+                break;
+            }
+        }
+
+        Ok(refs)
+    }
+
     /// Extracts any references found in a statement, if they exist.
     ///
     /// References can be found hidden in statements where the package identifier is
@@ -551,4 +896,217 @@ endmodule
             ]
         );
     }
+
+    #[test]
+    fn ut_into_dot_graph() {
+        let code = r#"
+module top(clk);
+    input clk;
+    leaf a0(.clk(clk));
+    leaf a1(.clk(clk));
+endmodule
+
+module leaf(clk);
+    input clk;
+endmodule
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let dot = into_dot_graph(&symbols);
+
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        // repeated instantiations of `leaf` collapse into a single edge
+        assert_eq!(dot.matches("top -> leaf;").count(), 1);
+    }
+
+    #[test]
+    fn ut_analyze_unused_element() {
+        let code = r#"
+module top(clk);
+    input clk;
+    leaf a0(.clk(clk));
+endmodule
+
+module leaf(clk);
+    input clk;
+endmodule
+
+module orphan(clk);
+    input clk;
+endmodule
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let diagnostics = analyze(&symbols);
+
+        assert_eq!(diagnostics.len(), 1);
+        match &diagnostics[0] {
+            AnalyzerError::UnusedElement(name, _) => {
+                assert_eq!(name, &Identifier::from_str("orphan").unwrap())
+            }
+        }
+    }
+
+    #[test]
+    fn ut_read_collects_all_errors() {
+        let code = r#"
+package pkg1
+endpackage
+
+package pkg2
+endpackage
+"#;
+        let errors = match SystemVerilogParser::read(&code) {
+            Ok(_) => panic!("expected a parsing error"),
+            Err(e) => e,
+        };
+        // both malformed packages are reported, not just the first
+        assert_eq!(errors.0.len(), 2);
+        // each error still carries the position of the offending token
+        assert!(errors.0.iter().all(|e| e.position().is_some()));
+        let rendered = errors.render(&code, "top.sv");
+        assert_eq!(rendered.matches("error: ").count(), 2);
+        assert_eq!(rendered.matches("--> top.sv").count(), 2);
+    }
+
+    #[test]
+    fn ut_program_collects_instance_and_import_refs() {
+        let code = r#"
+package defs;
+endpackage
+
+module dut(clk);
+    input clk;
+endmodule
+
+program test_bench(clk);
+    input clk;
+    import defs::*;
+    dut u_dut(.clk(clk));
+endprogram
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+
+        let program = symbols
+            .iter()
+            .find(|s| matches!(s, SystemVerilogSymbol::Program(_)))
+            .unwrap();
+        assert_eq!(program.as_name().unwrap(), &Identifier::from_str("test_bench").unwrap());
+
+        let refs: Vec<String> = program.get_refs().iter().map(|r| r.to_string()).collect();
+        assert!(refs.iter().any(|r| r == "dut"));
+        assert!(refs.iter().any(|r| r == "defs"));
+
+        // participates in the dependency graph alongside other design elements
+        let dot = into_dot_graph(&symbols);
+        assert!(dot.contains("test_bench -> dut;"));
+    }
+
+    #[test]
+    fn ut_quote_dot_id() {
+        assert_eq!(quote_dot_id("top"), "top");
+        assert_eq!(quote_dot_id("pkg.unit"), "\"pkg.unit\"");
+        assert_eq!(quote_dot_id("has\"quote"), "\"has\\\"quote\"");
+    }
+
+    #[test]
+    fn ut_elaborate_compilation_order() {
+        let code = r#"
+module top(clk);
+    input clk;
+    mid u_mid(.clk(clk));
+endmodule
+
+module mid(clk);
+    input clk;
+    leaf u_leaf(.clk(clk));
+endmodule
+
+module leaf(clk);
+    input clk;
+endmodule
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let elaboration = elaborate(&symbols);
+
+        assert!(elaboration.get_unresolved().is_empty());
+        assert!(elaboration.find_cycle().is_none());
+
+        // `top` depends on `mid`, `mid` depends on `leaf`
+        assert_eq!(elaboration.get_dependencies("top"), vec!["mid"]);
+        assert_eq!(elaboration.get_dependencies("mid"), vec!["leaf"]);
+        assert!(elaboration.get_dependencies("leaf").is_empty());
+
+        // dependencies appear before their dependents
+        let order = elaboration.compilation_order();
+        let leaf_pos = order.iter().position(|n| n == &"leaf").unwrap();
+        let mid_pos = order.iter().position(|n| n == &"mid").unwrap();
+        let top_pos = order.iter().position(|n| n == &"top").unwrap();
+        assert!(leaf_pos < mid_pos);
+        assert!(mid_pos < top_pos);
+    }
+
+    #[test]
+    fn ut_elaborate_unresolved_reference() {
+        let code = r#"
+module top(clk);
+    input clk;
+    missing u_missing(.clk(clk));
+endmodule
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let elaboration = elaborate(&symbols);
+
+        let unresolved: Vec<String> = elaboration
+            .get_unresolved()
+            .iter()
+            .map(|r| r.to_string())
+            .collect();
+        assert!(unresolved.iter().any(|r| r == "missing"));
+    }
+
+    #[test]
+    fn ut_elaborate_detects_cycle() {
+        let code = r#"
+module a(clk);
+    input clk;
+    b u_b(.clk(clk));
+endmodule
+
+module b(clk);
+    input clk;
+    a u_a(.clk(clk));
+endmodule
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let elaboration = elaborate(&symbols);
+
+        let cycle = elaboration.find_cycle().unwrap();
+        assert_eq!(cycle.len(), 2);
+        assert!(cycle.contains(&String::from("a")));
+        assert!(cycle.contains(&String::from("b")));
+    }
+
+    #[test]
+    fn ut_bind_directive_collects_refs() {
+        let code = r#"
+module dut(clk);
+    input clk;
+endmodule
+
+module checker_inst(clk);
+    input clk;
+endmodule
+
+bind dut checker_inst(.clk(clk));
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+
+        let dut = symbols
+            .iter()
+            .find(|s| s.as_name().map(|n| n.to_string()) == Some(String::from("dut")))
+            .unwrap();
+        let refs: Vec<String> = dut.get_refs().iter().map(|r| r.to_string()).collect();
+        assert!(refs.iter().any(|r| r == "dut"));
+        assert!(refs.iter().any(|r| r == "checker_inst"));
+    }
 }
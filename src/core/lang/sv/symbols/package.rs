@@ -74,13 +74,16 @@ impl Package {
         // take package name
         let name = match tokens.next().take().unwrap().take() {
             SystemVerilogToken::Identifier(id) => id,
-            _ => return Err(SystemVerilogError::Vague),
+            _ => return Err(SystemVerilogError::Vague(pos.clone())),
         };
 
         // take terminator ';'
         let t = tokens.next().take().unwrap();
         if t.as_type().check_delimiter(&Operator::Terminator) == false {
-            return Err(SystemVerilogError::ExpectingOperator(Operator::Terminator))
+            return Err(SystemVerilogError::ExpectingOperator(
+                Operator::Terminator,
+                t.locate().clone(),
+            ));
         }
 
         let mut refs = RefSet::new();
@@ -88,7 +91,10 @@ impl Package {
         // parse until finding `endpackage`
         while let Some(t) = tokens.next() {
             if t.as_type().is_eof() == true {
-                return Err(SystemVerilogError::ExpectingKeyword(Keyword::Endpackage));
+                return Err(SystemVerilogError::ExpectingKeyword(
+                    Keyword::Endpackage,
+                    t.locate().clone(),
+                ));
             } else if t.as_type().check_keyword(&Keyword::Endpackage) {
                 // exit the loop for parsing the package
                 break;
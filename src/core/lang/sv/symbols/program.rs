@@ -0,0 +1,121 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::iter::Peekable;
+
+use crate::core::lang::{
+    lexer::{Position, Token},
+    reference::RefSet,
+    sv::{
+        error::SystemVerilogError,
+        token::{identifier::Identifier, keyword::Keyword, operator::Operator, token::SystemVerilogToken},
+    },
+    verilog::symbols::VerilogSymbol,
+};
+
+use super::SystemVerilogSymbol;
+
+#[derive(Debug, PartialEq)]
+pub struct Program {
+    name: Identifier,
+    refs: RefSet,
+    pos: Position,
+}
+
+impl Program {
+    pub fn get_name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn get_position(&self) -> &Position {
+        &self.pos
+    }
+
+    pub fn get_refs(&self) -> &RefSet {
+        &self.refs
+    }
+
+    pub fn extend_refs(&mut self, refs: RefSet) {
+        self.refs.extend(refs);
+    }
+}
+
+impl Program {
+    pub fn from_tokens<I>(
+        tokens: &mut Peekable<I>,
+        pos: Position,
+    ) -> Result<Self, SystemVerilogError>
+    where
+        I: Iterator<Item = Token<SystemVerilogToken>>,
+    {
+        // take optional lifetime specifier
+        if let Some(maybe_lifetime) = tokens.peek() {
+            if maybe_lifetime.as_type().check_keyword(&Keyword::Automatic)
+                || maybe_lifetime.as_type().check_keyword(&Keyword::Static)
+            {
+                tokens.next().unwrap();
+            }
+        }
+
+        // take program name
+        let name = match tokens.next().take().unwrap().take() {
+            SystemVerilogToken::Identifier(id) => id,
+            _ => return Err(SystemVerilogError::Vague(pos.clone())),
+        };
+
+        let mut refs = RefSet::new();
+
+        // take all import statements and the optional port list/declaration
+        while let Some(t) = tokens.peek() {
+            if t.as_type().check_keyword(&Keyword::Import) {
+                let _ = tokens.next().unwrap();
+                let i_refs = SystemVerilogSymbol::parse_import_statement(tokens)?;
+                refs.extend(i_refs);
+            } else {
+                break;
+            }
+        }
+
+        let (_params, _ports, d_refs) = VerilogSymbol::parse_module_declaration(tokens)?;
+        refs.extend(d_refs);
+
+        // parse until finding `endprogram`, collecting references to any
+        // modules instantiated and packages imported along the way
+        while let Some(t) = tokens.next() {
+            if t.as_type().is_eof() == true {
+                return Err(SystemVerilogError::ExpectingKeyword(
+                    Keyword::Endprogram,
+                    t.locate().clone(),
+                ));
+            } else if t.as_type().check_keyword(&Keyword::Endprogram) {
+                // exit the loop for parsing the program
+                break;
+            } else if t.as_type().check_keyword(&Keyword::Import) {
+                let i_refs = SystemVerilogSymbol::parse_import_statement(tokens)?;
+                refs.extend(i_refs);
+            } else if let Some(stmt) = VerilogSymbol::into_next_statement(t, tokens)? {
+                VerilogSymbol::handle_statement(stmt, None, None, &mut refs, None)?;
+            }
+        }
+
+        Ok(Program {
+            name: name,
+            refs: refs,
+            pos: pos,
+        })
+    }
+}
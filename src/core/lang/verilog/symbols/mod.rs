@@ -34,9 +34,11 @@ use std::str::FromStr;
 
 pub mod config;
 pub mod module;
+pub mod primitive;
 
 use config::Config;
 use module::Module;
+use primitive::Primitive;
 
 pub type Statement = Vec<Token<SystemVerilogToken>>;
 
@@ -57,6 +59,7 @@ fn statement_to_string(stmt: &Statement) -> String {
 pub enum VerilogSymbol {
     Module(Module),
     Config(Config),
+    Primitive(Primitive),
 }
 
 impl VerilogSymbol {
@@ -64,6 +67,7 @@ impl VerilogSymbol {
         match &self {
             Self::Module(m) => Some(m.get_name()),
             Self::Config(c) => Some(c.get_name()),
+            Self::Primitive(p) => Some(p.get_name()),
         }
     }
 
@@ -71,6 +75,7 @@ impl VerilogSymbol {
         match self {
             Self::Module(m) => m.get_position(),
             Self::Config(c) => c.get_position(),
+            Self::Primitive(p) => p.get_position(),
         }
     }
 
@@ -95,6 +100,7 @@ impl VerilogSymbol {
         match &self {
             Self::Module(m) => m.get_refs(),
             Self::Config(c) => c.get_refs(),
+            Self::Primitive(p) => p.get_refs(),
         }
     }
 }
@@ -180,6 +186,14 @@ impl Parse<VerilogToken> for VerilogParser {
                         Err(e) => Err(e),
                     },
                 );
+            // create primitive symbol (a user-defined primitive declared with `primitive`)
+            } else if t.as_type().check_keyword(&Keyword::Primitive) {
+                symbols.push(
+                    match Primitive::from_tokens(&mut tokens, t.into_position()) {
+                        Ok(prim) => Ok(Symbol::new(VerilogSymbol::Primitive(prim))),
+                        Err(e) => Err(e),
+                    },
+                );
             // skip any potential illegal/unknown tokens at global scale
             } else if t.as_type().is_eof() == false {
                 // println!("{:?}", t);
@@ -17,7 +17,7 @@
 
 use thiserror::Error;
 
-#[derive(Debug, Error, PartialEq)]
+#[derive(Debug, Error, PartialEq, Clone)]
 pub enum VhdlError {
     #[error("{0}")]
     Any(String),
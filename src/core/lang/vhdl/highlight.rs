@@ -55,6 +55,7 @@ mod palette {
 pub const NUMBERS: Rgb = GOLDEN_ROD;
 pub const CHARS: Rgb = SEAFOAM_GREEN;
 pub const STRINGS: Rgb = BURNT_ORANGE;
+pub const INVALID: Rgb = RED;
 
 /* `orbit get` colorings */
 pub const SIGNAL_DEC_IDENTIFIER: Rgb = LT_SKY_BLUE;
@@ -5,6 +5,7 @@ use crate::core::lang::reference::RefSet;
 use crate::core::lang::vhdl::symbols::VHDLParser;
 use crate::core::lang::vhdl::token::identifier::Identifier;
 use crate::util::anyerror::CodeFault;
+use crate::util::environment;
 use crate::util::filesystem;
 use crate::{core::ip::IpSpec, error::Hint};
 use std::{collections::HashMap, path::PathBuf, str::FromStr};
@@ -136,94 +137,127 @@ impl PartialEq for Unit {
 
 impl Eq for Unit {}
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
-    let mut result: HashMap<Identifier, PrimaryUnit> = HashMap::new();
-    // iterate through all source files
-    for source_file in files {
-        // only read the HDL files
-        if crate::core::fileset::is_vhdl(&source_file) == true {
-            // parse text into VHDL symbols
-            let contents = match std::fs::read_to_string(&source_file) {
-                Ok(dump) => dump,
-                Err(e) => return Err(CodeFault(Some(source_file.clone()), Box::new(e))),
-            };
-            let symbols = match VHDLParser::read(&contents) {
-                Ok(s) => s.into_symbols(),
-                Err(e) => Err(CodeFault(Some(source_file.clone()), Box::new(e)))?,
+/// Parses a single source file into its owned map of primary design units.
+///
+/// Kept separate from [collect_units] so the per-file parsing can be fanned
+/// out across threads; each file is independent until its results are
+/// merged back into the combined map.
+fn collect_units_from_file(
+    source_file: &String,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+    // parse text into VHDL symbols
+    let contents = match std::fs::read_to_string(&source_file) {
+        Ok(dump) => dump,
+        Err(e) => return Err(CodeFault(Some(source_file.clone()), Box::new(e))),
+    };
+    let symbols = match VHDLParser::read(&contents) {
+        Ok(s) => s.into_symbols(),
+        Err(e) => Err(CodeFault(Some(source_file.clone()), Box::new(e)))?,
+    };
+
+    let (pri_nodes, sub_nodes): (Vec<VhdlSymbol>, Vec<VhdlSymbol>) =
+        symbols.into_iter().partition(|s| s.is_primary());
+
+    // assemble primary nodes
+    let mut pri_units: HashMap<Identifier, PrimaryUnit> = pri_nodes
+        .into_iter()
+        .map(|sym| {
+            let name = sym.get_name().unwrap().clone();
+            let shape = match &sym {
+                VhdlSymbol::Entity(_) => Some(PrimaryShape::Entity),
+                VhdlSymbol::Package(_) => Some(PrimaryShape::Package),
+                VhdlSymbol::Configuration(_) => Some(PrimaryShape::Configuration),
+                VhdlSymbol::Context(_) => Some(PrimaryShape::Configuration),
+                VhdlSymbol::Architecture(_) => {
+                    panic!("architectures cannot be here")
+                }
+                // package bodies are usually in same design file as package
+                VhdlSymbol::PackageBody(_) => {
+                    panic!("package bodies cannot be here")
+                }
             };
+            match shape {
+                Some(s) => (
+                    name.clone(),
+                    PrimaryUnit {
+                        shape: s,
+                        unit: Unit {
+                            name: name,
+                            symbol: Some(sym),
+                            source: source_file.clone(),
+                        },
+                    },
+                ),
+                None => panic!("must be a primary design unit"),
+            }
+        })
+        .collect();
+
+    // assemble secondary nodes
+    sub_nodes
+        .into_iter()
+        .map(|n| match n {
+            VhdlSymbol::Architecture(arch) => SubUnit::from_arch(arch),
+            VhdlSymbol::PackageBody(pkg_body) => SubUnit::from_body(pkg_body),
+            _ => panic!("primary design units cannot be here"),
+        })
+        .for_each(|n| {
+            if let Some(owner) = pri_units.get_mut(n.get_entity()) {
+                owner.steal_refs(n.into_refs());
+            }
+        });
 
-            let (pri_nodes, sub_nodes): (Vec<VhdlSymbol>, Vec<VhdlSymbol>) =
-                symbols.into_iter().partition(|s| s.is_primary());
+    Ok(pri_units)
+}
 
-            // assemble primary nodes
-            let mut pri_units: HashMap<Identifier, PrimaryUnit> = pri_nodes
-                .into_iter()
-                .map(|sym| {
-                    let name = sym.get_name().unwrap().clone();
-                    let shape = match &sym {
-                        VhdlSymbol::Entity(_) => Some(PrimaryShape::Entity),
-                        VhdlSymbol::Package(_) => Some(PrimaryShape::Package),
-                        VhdlSymbol::Configuration(_) => Some(PrimaryShape::Configuration),
-                        VhdlSymbol::Context(_) => Some(PrimaryShape::Configuration),
-                        VhdlSymbol::Architecture(_) => {
-                            panic!("architectures cannot be here")
-                        }
-                        // package bodies are usually in same design file as package
-                        VhdlSymbol::PackageBody(_) => {
-                            panic!("package bodies cannot be here")
-                        }
-                    };
-                    match shape {
-                        Some(s) => (
-                            name.clone(),
-                            PrimaryUnit {
-                                shape: s,
-                                unit: Unit {
-                                    name: name,
-                                    symbol: Some(sym),
-                                    source: source_file.clone(),
-                                },
-                            },
-                        ),
-                        None => panic!("must be a primary design unit"),
-                    }
-                })
-                .collect();
+pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+    // only read the HDL files
+    let vhdl_files: Vec<&String> = files
+        .iter()
+        .filter(|f| crate::core::fileset::is_vhdl(f) == true)
+        .collect();
 
-            // assemble secondary nodes
-            sub_nodes
-                .into_iter()
-                .map(|n| match n {
-                    VhdlSymbol::Architecture(arch) => SubUnit::from_arch(arch),
-                    VhdlSymbol::PackageBody(pkg_body) => SubUnit::from_body(pkg_body),
-                    _ => panic!("primary design units cannot be here"),
-                })
-                .for_each(|n| {
-                    if let Some(owner) = pri_units.get_mut(n.get_entity()) {
-                        owner.steal_refs(n.into_refs());
-                    }
-                });
+    // parse each file independently, in parallel unless asked to run
+    // single-threaded (see [environment::ORBIT_SINGLE_THREADED])
+    let file_units: Vec<HashMap<Identifier, PrimaryUnit>> = if environment::is_single_threaded()
+        == true
+    {
+        vhdl_files
+            .into_iter()
+            .map(|source_file| collect_units_from_file(source_file))
+            .collect::<Result<Vec<_>, CodeFault>>()?
+    } else {
+        use rayon::prelude::*;
+        vhdl_files
+            .into_par_iter()
+            .map(|source_file| collect_units_from_file(source_file))
+            .collect::<Result<Vec<_>, CodeFault>>()?
+    };
 
-            for (_key, primary) in pri_units {
-                if let Some(dupe) = result.insert(primary.get_iden().clone(), primary) {
-                    return Err(CodeFault(
-                        None,
-                        Box::new(VhdlIdentifierError::DuplicateIdentifier(
-                            dupe.get_iden().to_string(),
-                            PathBuf::from(source_file),
-                            result
-                                .get(dupe.get_iden())
-                                .unwrap()
-                                .get_unit()
-                                .get_symbol()
-                                .unwrap()
-                                .get_position()
-                                .clone(),
-                            PathBuf::from(dupe.get_unit().get_source_file()),
-                            dupe.get_unit().get_symbol().unwrap().get_position().clone(),
-                        )),
-                    ))?;
-                }
+    // merge the per-file maps serially so duplicate identifiers are caught
+    // deterministically regardless of which thread finished first
+    let mut result: HashMap<Identifier, PrimaryUnit> = HashMap::new();
+    for pri_units in file_units {
+        for (_key, primary) in pri_units {
+            let source_file = primary.get_unit().get_source_file().to_string();
+            if let Some(dupe) = result.insert(primary.get_iden().clone(), primary) {
+                return Err(CodeFault(
+                    None,
+                    Box::new(VhdlIdentifierError::DuplicateIdentifier(
+                        dupe.get_iden().to_string(),
+                        PathBuf::from(&source_file),
+                        result
+                            .get(dupe.get_iden())
+                            .unwrap()
+                            .get_unit()
+                            .get_symbol()
+                            .unwrap()
+                            .get_position()
+                            .clone(),
+                        PathBuf::from(dupe.get_unit().get_source_file()),
+                        dupe.get_unit().get_symbol().unwrap().get_position().clone(),
+                    )),
+                ))?;
             }
         }
     }
@@ -22,17 +22,97 @@ use colored::ColoredString;
 use colored::Colorize;
 use std::fmt::Display;
 
+/// A synthesis/tool directive embedded in a single-line comment, named
+/// after the specific marker text [Comment::classify] detected.
+#[derive(Debug, PartialEq, Clone)]
+pub enum CommentDirective {
+    /// `-- synthesis translate_off`
+    TranslateOff,
+    /// `-- synthesis translate_on`
+    TranslateOn,
+    /// `-- vhdl_comp_off`
+    VhdlCompOff,
+    /// `-- vhdl_comp_on`
+    VhdlCompOn,
+    /// Any other `-- pragma ...` tool directive.
+    Pragma,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Comment {
     Single(String),
     Delimited(String),
+    /// A single-line comment opened with `--!`, carrying doc text meant for
+    /// a future documentation generator to pull entity/port descriptions
+    /// from. Holds the raw body, `!` marker included; see
+    /// [Comment::doc_text] for the marker stripped off.
+    Doc(String),
+    /// A single-line comment recognized as a synthesis/tool directive (a
+    /// `synthesis translate_off`/`_on` region, a `vhdl_comp_off`/`_on`
+    /// region, or a bare `pragma`). Holds the raw body alongside the
+    /// detected [CommentDirective].
+    Directive(String, CommentDirective),
 }
 
 impl Comment {
+    /// Classifies a freshly-collected single-line comment body (the text
+    /// after `--`, before this call wraps it in a `Comment`) by its
+    /// leading marker.
+    ///
+    /// Mirrors the `DocComment`/`CommentType` split popularized by gluon's
+    /// lexer: a leading `!` marks a doc comment, and a handful of
+    /// well-known synthesis/tool directives are recognized so downstream
+    /// analysis can flag a translate-off region instead of treating the
+    /// comment as ordinary prose (see
+    /// [crate::commands::plan::Plan::create_vhdl_node]). Anything else
+    /// stays a plain [Self::Single].
+    pub fn classify(note: String) -> Self {
+        if note.starts_with('!') {
+            return Self::Doc(note);
+        }
+        let trimmed = note.trim_start().to_ascii_lowercase();
+        let directive = if trimmed.starts_with("synthesis translate_off") {
+            Some(CommentDirective::TranslateOff)
+        } else if trimmed.starts_with("synthesis translate_on") {
+            Some(CommentDirective::TranslateOn)
+        } else if trimmed.starts_with("vhdl_comp_off") {
+            Some(CommentDirective::VhdlCompOff)
+        } else if trimmed.starts_with("vhdl_comp_on") {
+            Some(CommentDirective::VhdlCompOn)
+        } else if trimmed.starts_with("pragma") {
+            Some(CommentDirective::Pragma)
+        } else {
+            None
+        };
+        match directive {
+            Some(d) => Self::Directive(note, d),
+            None => Self::Single(note),
+        }
+    }
+
+    /// Returns this comment's attached doc text (its body with the leading
+    /// `!` marker stripped), if it is a [Self::Doc] comment.
+    pub fn doc_text(&self) -> Option<&str> {
+        match self {
+            Self::Doc(note) => Some(&note[1..]),
+            _ => None,
+        }
+    }
+
+    /// Returns the synthesis/tool directive this comment carries, if any.
+    pub fn directive(&self) -> Option<&CommentDirective> {
+        match self {
+            Self::Directive(_, d) => Some(d),
+            _ => None,
+        }
+    }
+
     fn as_str(&self) -> &str {
         match self {
             Self::Single(note) => note.as_ref(),
             Self::Delimited(note) => note.as_ref(),
+            Self::Doc(note) => note.as_ref(),
+            Self::Directive(note, _) => note.as_ref(),
         }
     }
 
@@ -49,12 +129,12 @@ impl Comment {
             }
         }
         match self {
-            Self::Single(_) => (),
             // increment to handle the closing delimiters */
             Self::Delimited(_) => {
                 pos.next_col();
                 pos.next_col();
             }
+            Self::Single(_) | Self::Doc(_) | Self::Directive(_, _) => (),
         }
         pos
     }
@@ -65,6 +145,8 @@ impl Display for Comment {
         match self {
             Self::Single(c) => write!(f, "--{}", c),
             Self::Delimited(c) => write!(f, "/*{}*/", c),
+            Self::Doc(c) => write!(f, "--{}", c),
+            Self::Directive(c, _) => write!(f, "--{}", c),
         }
     }
 }
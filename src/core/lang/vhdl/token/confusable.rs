@@ -0,0 +1,244 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::super::super::lexer::{Position, Token};
+use super::{Identifier, VhdlToken};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// A Unicode script a code point belongs to, coarse enough to catch the
+/// homoglyph pairs most likely to be mistaken for one another in an
+/// identifier (VHDL-2019 permits full Unicode source text).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    /// Digits, punctuation, and anything else script-neutral; never
+    /// conflicts with another script on its own.
+    Common,
+}
+
+impl Script {
+    /// Classifies `c` by the Unicode block it falls in.
+    fn of(c: char) -> Self {
+        match c {
+            '0'..='9' | '_' => Self::Common,
+            '\u{0370}'..='\u{03FF}' => Self::Greek,
+            '\u{0400}'..='\u{04FF}' => Self::Cyrillic,
+            _ => Self::Latin,
+        }
+    }
+}
+
+/// Looks up the ASCII letter `c` is commonly confused with, if any.
+///
+/// This is a small, hand-picked table of the homoglyph pairs that show up
+/// in practice (Cyrillic and Greek letters that are visually identical to
+/// a Latin letter), not a full Unicode confusables database.
+fn confusable_skeleton(c: char) -> char {
+    match c {
+        // Cyrillic
+        'а' => 'a',
+        'А' => 'A',
+        'е' => 'e',
+        'Е' => 'E',
+        'о' => 'o',
+        'О' => 'O',
+        'р' => 'p',
+        'Р' => 'P',
+        'с' => 'c',
+        'С' => 'C',
+        'у' => 'y',
+        'У' => 'Y',
+        'х' => 'x',
+        'Х' => 'X',
+        'і' => 'i',
+        'Ӏ' => 'I',
+        'к' => 'k',
+        'К' => 'K',
+        'м' => 'm',
+        'М' => 'M',
+        'н' => 'h',
+        'Н' => 'H',
+        'в' => 'b',
+        'В' => 'B',
+        'т' => 't',
+        'Т' => 'T',
+        // Greek
+        'α' => 'a',
+        'Α' => 'A',
+        'ο' => 'o',
+        'Ο' => 'O',
+        'ρ' => 'p',
+        'Ρ' => 'P',
+        'υ' => 'y',
+        'Υ' => 'Y',
+        'κ' => 'k',
+        'Κ' => 'K',
+        'ν' => 'v',
+        'Ν' => 'N',
+        'χ' => 'x',
+        'Χ' => 'X',
+        'τ' => 't',
+        'Τ' => 'T',
+        'β' => 'b',
+        'Β' => 'B',
+        _ => c,
+    }
+}
+
+/// Why [detect_confusables] flagged an identifier.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConfusableReason {
+    /// The identifier itself draws letters from more than one script (e.g.
+    /// a Latin `a` alongside a Cyrillic `р`).
+    MixedScript,
+    /// The identifier is entirely non-ASCII, but its confusable-skeleton
+    /// collides with a plain ASCII identifier also defined in this file
+    /// (the identifier text is given here).
+    ConfusableWithAscii(String),
+}
+
+impl Display for ConfusableReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MixedScript => write!(f, "identifier mixes multiple unicode scripts"),
+            Self::ConfusableWithAscii(other) => {
+                write!(f, "identifier is confusable with '{}'", other)
+            }
+        }
+    }
+}
+
+/// A single homoglyph/mixed-script finding from [detect_confusables].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConfusableDiagnostic {
+    pub identifier: String,
+    pub position: Position,
+    pub reason: ConfusableReason,
+}
+
+/// Scans a tokenized VHDL file for identifiers that are a homoglyph hazard:
+/// either an identifier mixing multiple Unicode scripts, or a non-ASCII
+/// identifier whose confusable-skeleton collides with an unrelated ASCII
+/// identifier defined elsewhere in the same token stream.
+///
+/// This is purely a lexical/textual analysis: it does not know about scope
+/// or declarations, so two identically-skeletoned identifiers anywhere in
+/// the file are flagged even if they would never actually resolve to one
+/// another. That is a deliberately conservative choice for a package
+/// manager, where picking up the wrong design unit silently is the
+/// expensive failure mode.
+pub fn detect_confusables(tokens: &[Token<VhdlToken>]) -> Vec<ConfusableDiagnostic> {
+    let mut ascii_skeletons: HashMap<String, String> = HashMap::new();
+    let mut findings = Vec::new();
+
+    for tk in tokens {
+        let id = match tk.as_type() {
+            VhdlToken::Identifier(id) => id,
+            _ => continue,
+        };
+        let text = identifier_text(id);
+        if text.is_ascii() {
+            ascii_skeletons
+                .entry(text.to_string())
+                .or_insert_with(|| text.to_string());
+            continue;
+        }
+
+        let scripts: Vec<Script> = text
+            .chars()
+            .map(Script::of)
+            .filter(|s| *s != Script::Common)
+            .collect();
+        let mixes_scripts = scripts.windows(2).any(|w| w[0] != w[1]);
+        if mixes_scripts {
+            findings.push(ConfusableDiagnostic {
+                identifier: text.to_string(),
+                position: tk.locate().clone(),
+                reason: ConfusableReason::MixedScript,
+            });
+            continue;
+        }
+
+        let skeleton: String = text.chars().map(confusable_skeleton).collect();
+        if let Some(ascii_match) = ascii_skeletons.get(&skeleton) {
+            findings.push(ConfusableDiagnostic {
+                identifier: text.to_string(),
+                position: tk.locate().clone(),
+                reason: ConfusableReason::ConfusableWithAscii(ascii_match.clone()),
+            });
+        }
+    }
+
+    findings
+}
+
+fn identifier_text(id: &Identifier) -> &str {
+    match id {
+        Identifier::Basic(s) => s.as_str(),
+        Identifier::Extended(s) => s.as_str(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::vhdl::token::keyword::Keyword;
+
+    fn ident_tok(text: &str, line: usize) -> Token<VhdlToken> {
+        Token::new(
+            VhdlToken::Identifier(Identifier::Basic(text.to_owned())),
+            Position::place(line, 0),
+        )
+    }
+
+    #[test]
+    fn flags_mixed_script_identifier() {
+        // 'а' here is Cyrillic U+0430, not Latin 'a'
+        let tokens = vec![ident_tok("fooа", 1)];
+        let findings = detect_confusables(&tokens);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].reason, ConfusableReason::MixedScript);
+    }
+
+    #[test]
+    fn flags_confusable_with_ascii_identifier() {
+        let tokens = vec![
+            ident_tok("acc", 1),
+            // every letter here is Cyrillic, but skeletons to "acc"
+            ident_tok("асс", 2),
+        ];
+        let findings = detect_confusables(&tokens);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].reason,
+            ConfusableReason::ConfusableWithAscii("acc".to_owned())
+        );
+    }
+
+    #[test]
+    fn plain_ascii_identifiers_are_not_flagged() {
+        let tokens = vec![
+            ident_tok("clk", 1),
+            ident_tok("rst_n", 2),
+            Token::new(VhdlToken::Keyword(Keyword::Entity), Position::place(3, 0)),
+        ];
+        assert_eq!(detect_confusables(&tokens).is_empty(), true);
+    }
+}
@@ -0,0 +1,189 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::super::super::lexer::{Position, Span};
+use super::super::error::VhdlError;
+use std::fmt::Display;
+
+/// A classified lexing problem, for tools (editor/LSP integrations) that
+/// want to report every lexical mistake in a file at once rather than
+/// `.unwrap()`-ing the first one. Built from the same [VhdlError] the
+/// strict tokenizer already returns; see [LexMessage::classify].
+#[derive(Debug, PartialEq, Clone)]
+pub enum LexMessage {
+    /// A `/* ... */` comment with no closing `*/` before end-of-file.
+    UnclosedDelimitedComment,
+    /// A `"..."` string literal with no closing quote.
+    UnterminatedStringLiteral,
+    /// A `\...\` extended identifier that was never closed or had trailing
+    /// characters after its closing backslash.
+    InvalidExtendedIdentifier,
+    /// A based literal (`base#digits#` or a bit string literal) whose
+    /// digits don't fit the base/specifier it declared.
+    InvalidBasedLiteral { base: String, found: char },
+    /// A single character that didn't fit any recognized token shape.
+    UnexpectedCharacter(char),
+    /// Any other lexical error, kept verbatim since not every message a
+    /// `consume_*` helper can raise maps cleanly onto one of the variants
+    /// above.
+    Other(String),
+}
+
+impl LexMessage {
+    /// Classifies the `(invalid_text, VhdlError)` pair a [super::VhdlToken::Invalid]
+    /// token carries, using the shape of the text that was being lexed when
+    /// the error occurred (its leading delimiter) as the primary signal,
+    /// since [VhdlError] itself is just a free-form message.
+    pub fn classify(invalid_text: &str, err: &VhdlError) -> Self {
+        let mut chars = invalid_text.chars();
+        let first = chars.next();
+        match first {
+            Some('/') if invalid_text.starts_with("/*") => Self::UnclosedDelimitedComment,
+            Some('"') => Self::UnterminatedStringLiteral,
+            Some('\\') => Self::InvalidExtendedIdentifier,
+            Some(c) if c.is_ascii_digit() && err.to_string().contains("based") => {
+                Self::InvalidBasedLiteral {
+                    base: invalid_text
+                        .chars()
+                        .take_while(|c| c.is_ascii_digit())
+                        .collect(),
+                    found: chars.find(|c| !c.is_ascii_digit() && *c != '#').unwrap_or('?'),
+                }
+            }
+            Some(c) if invalid_text.chars().count() == 1 => Self::UnexpectedCharacter(c),
+            _ => Self::Other(err.to_string()),
+        }
+    }
+}
+
+impl Display for LexMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnclosedDelimitedComment => write!(f, "unclosed delimited comment"),
+            Self::UnterminatedStringLiteral => write!(f, "unterminated string literal"),
+            Self::InvalidExtendedIdentifier => write!(f, "invalid extended identifier"),
+            Self::InvalidBasedLiteral { base, found } => {
+                write!(f, "invalid digit '{}' for base {}", found, base)
+            }
+            Self::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            Self::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+/// A single diagnostic emitted while lexing, pairing a [LexMessage] with
+/// where it happened. `file` is left for a caller to fill in (the
+/// tokenizer itself only ever sees raw source text, never a path), so it
+/// is `None` fresh out of [super::tokenizer::VhdlTokenizer::tokenize_with_diagnostics].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Log {
+    pub message: LexMessage,
+    pub span: Span,
+    pub file: Option<String>,
+}
+
+impl Display for Log {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}{} {}", file, self.span.start, self.message),
+            None => write!(f, "{} {}", self.span.start, self.message),
+        }
+    }
+}
+
+/// An accumulating collector of [Log]s, gathered over a full lexing pass
+/// instead of aborting at the first one.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Logger {
+    logs: Vec<Log>,
+}
+
+impl Logger {
+    pub fn new() -> Self {
+        Self { logs: Vec::new() }
+    }
+
+    pub fn push(&mut self, log: Log) {
+        self.logs.push(log);
+    }
+
+    pub fn logs(&self) -> &[Log] {
+        &self.logs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.logs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.logs.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_common_lex_errors() {
+        let err = VhdlError::Any(String::from("missing closing delimiter */"));
+        assert_eq!(
+            LexMessage::classify("/* never closes", &err),
+            LexMessage::UnclosedDelimitedComment
+        );
+
+        let err = VhdlError::Any(String::from("expecting closing delimiter"));
+        assert_eq!(
+            LexMessage::classify("\"never closes", &err),
+            LexMessage::UnterminatedStringLiteral
+        );
+
+        let err = VhdlError::Invalid(String::from("x"));
+        assert_eq!(
+            LexMessage::classify("\\never closes", &err),
+            LexMessage::InvalidExtendedIdentifier
+        );
+
+        let err = VhdlError::Any(String::from("invalid character in literal"));
+        assert_eq!(LexMessage::classify("@", &err), LexMessage::UnexpectedCharacter('@'));
+    }
+
+    #[test]
+    fn logger_accumulates_in_order() {
+        let mut logger = Logger::new();
+        assert_eq!(logger.is_empty(), true);
+        logger.push(Log {
+            message: LexMessage::UnexpectedCharacter('@'),
+            span: Span {
+                start: Position::place(1, 0),
+                end: Position::place(1, 1),
+            },
+            file: None,
+        });
+        logger.push(Log {
+            message: LexMessage::UnterminatedStringLiteral,
+            span: Span {
+                start: Position::place(2, 0),
+                end: Position::place(2, 5),
+            },
+            file: None,
+        });
+        assert_eq!(logger.len(), 2);
+        assert_eq!(logger.logs()[0].message, LexMessage::UnexpectedCharacter('@'));
+        assert_eq!(logger.logs()[1].message, LexMessage::UnterminatedStringLiteral);
+    }
+}
@@ -42,6 +42,232 @@ impl Display for BitStrLiteral {
     }
 }
 
+/// A single bit of IEEE 1164 `std_logic`: the nine-value logic system a
+/// bit string literal's digits expand into once meta-values (`X Z W L H U
+/// -`), not just `0`/`1`, are allowed (see [BitStrLiteral::eval]).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StdLogic {
+    /// `U`: uninitialized
+    U,
+    /// `X`: forcing unknown
+    X,
+    /// `0`: forcing 0
+    Zero,
+    /// `1`: forcing 1
+    One,
+    /// `Z`: high impedance
+    Z,
+    /// `W`: weak unknown
+    W,
+    /// `L`: weak 0
+    L,
+    /// `H`: weak 1
+    H,
+    /// `-`: don't care
+    DontCare,
+}
+
+impl StdLogic {
+    /// Interprets `c` as one of the nine `std_logic` values, if it is one.
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c.to_ascii_uppercase() {
+            'U' => Self::U,
+            'X' => Self::X,
+            '0' => Self::Zero,
+            '1' => Self::One,
+            'Z' => Self::Z,
+            'W' => Self::W,
+            'L' => Self::L,
+            'H' => Self::H,
+            '-' => Self::DontCare,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for StdLogic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::U => 'U',
+                Self::X => 'X',
+                Self::Zero => '0',
+                Self::One => '1',
+                Self::Z => 'Z',
+                Self::W => 'W',
+                Self::L => 'L',
+                Self::H => 'H',
+                Self::DontCare => '-',
+            }
+        )
+    }
+}
+
+/// The typed value a literal-bearing [super::VhdlToken] evaluates to (see
+/// [super::VhdlToken::eval]): an [AbstLiteral] evaluates to [Self::Integer]
+/// or [Self::Real], and a [BitStrLiteral] evaluates to [Self::BitVec].
+#[derive(Debug, PartialEq, Clone)]
+pub enum LiteralValue {
+    Integer(i128),
+    Real(f64),
+    BitVec(Vec<StdLogic>),
+}
+
+impl TryFrom<AbstLiteralValue> for LiteralValue {
+    type Error = VhdlError;
+
+    fn try_from(value: AbstLiteralValue) -> Result<Self, Self::Error> {
+        Ok(match value {
+            AbstLiteralValue::Integer(i) => Self::Integer(i.try_into().map_err(|_| {
+                VhdlError::Any(format!("literal value {} overflows a 128-bit integer", i))
+            })?),
+            AbstLiteralValue::Real(r) => Self::Real(r),
+        })
+    }
+}
+
+impl BitStrLiteral {
+    /// Converts a run of base-10 digits (a `D`-specifier bit string's body)
+    /// into the binary value it represents.
+    fn decimal_digits_to_bits(digits: &Vec<char>, s: &str) -> Result<Vec<bool>, VhdlError> {
+        let mut value: u128 = 0;
+        for c in digits {
+            let d = c.to_digit(10).ok_or_else(|| VhdlError::Invalid(c.to_string()))?;
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(d as u128))
+                .ok_or_else(|| VhdlError::Any(format!("bit string literal '{}' overflows", s)))?;
+        }
+        if value == 0 {
+            return Ok(vec![false]);
+        }
+        let mut bits = Vec::new();
+        while value > 0 {
+            bits.push(value & 1 == 1);
+            value >>= 1;
+        }
+        bits.reverse();
+        Ok(bits)
+    }
+
+    /// Evaluates this bit string literal into its [StdLogic] values, so the
+    /// VHDL-2008 extended bit string meta-values (`X Z W L H U -`) can be
+    /// represented alongside plain `0`/`1` digits. A meta-value digit
+    /// replicates across its whole
+    /// digit width (e.g. `X` in a hex literal becomes four `StdLogic::X`
+    /// bits); meta-values are only legal for `B`/`O`/`X` base specifiers —
+    /// a `D` (decimal) literal has no use for them and errors instead,
+    /// same as any other non-decimal-digit character would.
+    pub fn eval(&self) -> Result<Vec<StdLogic>, VhdlError> {
+        let s = &self.0;
+        let quote_pos = s.find(char_set::DOUBLE_QUOTE).ok_or_else(|| {
+            VhdlError::Any(format!("missing opening quote in bit string literal '{}'", s))
+        })?;
+        let prefix = &s[..quote_pos];
+        let body = &s[quote_pos + 1..s.len() - char_set::DOUBLE_QUOTE.len_utf8()];
+
+        let spec_start = prefix
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(prefix.len());
+        let width = match spec_start {
+            0 => None,
+            _ => Some(interpret_integer(&prefix[..spec_start])),
+        };
+        let base_spec = BaseSpec::from_str(&prefix[spec_start..])?;
+
+        let digits: Vec<char> = body.chars().filter(|c| c != &char_set::UNDERLINE).collect();
+
+        let mut bits: Vec<StdLogic> = match base_spec {
+            BaseSpec::D => Self::decimal_digits_to_bits(&digits, s)?
+                .into_iter()
+                .map(|b| if b { StdLogic::One } else { StdLogic::Zero })
+                .collect(),
+            _ => {
+                let (radix, bits_per_digit) = match base_spec {
+                    BaseSpec::B | BaseSpec::UB | BaseSpec::SB => (2, 1),
+                    BaseSpec::O | BaseSpec::UO | BaseSpec::SO => (8, 3),
+                    BaseSpec::X | BaseSpec::UX | BaseSpec::SX => (16, 4),
+                    BaseSpec::D => unreachable!(),
+                };
+                let mut bits = Vec::with_capacity(digits.len() * bits_per_digit);
+                for c in &digits {
+                    // a meta-value character (other than a plain '0'/'1',
+                    // which every base already decodes directly) replicates
+                    // across the whole digit's bit width
+                    if let Some(meta) = StdLogic::from_char(*c) {
+                        if *c != '0' && *c != '1' {
+                            bits.extend(std::iter::repeat(meta).take(bits_per_digit));
+                            continue;
+                        }
+                    }
+                    let d = c
+                        .to_digit(radix)
+                        .ok_or_else(|| VhdlError::Invalid(c.to_string()))?;
+                    for i in (0..bits_per_digit).rev() {
+                        bits.push(if (d >> i) & 1 == 1 {
+                            StdLogic::One
+                        } else {
+                            StdLogic::Zero
+                        });
+                    }
+                }
+                bits
+            }
+        };
+
+        let is_signed = match base_spec {
+            BaseSpec::SB | BaseSpec::SO | BaseSpec::SX => true,
+            _ => false,
+        };
+        if let Some(width) = width {
+            apply_width(&mut bits, width, is_signed, StdLogic::Zero, s)?;
+        }
+        Ok(bits)
+    }
+}
+
+/// Left-pads or left-truncates `bits` to exactly `width` elements in place,
+/// erroring if truncation would discard a value that doesn't match the
+/// fill value (`zero`, or the new leftmost bit when sign-extending/
+/// truncating a signed literal). Used by [BitStrLiteral::eval] to apply a
+/// bit string literal's explicit width, if it has one.
+fn apply_width<T: Clone + PartialEq>(
+    bits: &mut Vec<T>,
+    width: usize,
+    is_signed: bool,
+    zero: T,
+    s: &str,
+) -> Result<(), VhdlError> {
+    if bits.len() < width {
+        let pad = if is_signed {
+            bits.first().cloned().unwrap_or(zero)
+        } else {
+            zero
+        };
+        let mut padded = vec![pad; width - bits.len()];
+        padded.extend(bits.iter().cloned());
+        *bits = padded;
+    } else if bits.len() > width {
+        let excess = bits.len() - width;
+        let (dropped, kept) = bits.split_at(excess);
+        let fill = if is_signed {
+            kept.first().cloned().unwrap_or(zero)
+        } else {
+            zero
+        };
+        if dropped.iter().any(|b| *b != fill) {
+            return Err(VhdlError::Any(format!(
+                "bit string literal '{}' loses a significant bit truncating to width {}",
+                s, width
+            )));
+        }
+        *bits = kept.to_vec();
+    }
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum AbstLiteral {
     Decimal(String),
@@ -61,6 +287,116 @@ impl Display for AbstLiteral {
     }
 }
 
+/// The typed value a [AbstLiteral] evaluates to: an exact integer when the
+/// literal has neither a fraction nor a negative exponent, and a real
+/// otherwise.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AbstLiteralValue {
+    Integer(u128),
+    Real(f64),
+}
+
+impl AbstLiteral {
+    /// Evaluates this literal's text into the number it denotes, in the
+    /// spirit of the `litrs` crate: parse once, check for overflow, and
+    /// hand back a typed value instead of a string a caller has to
+    /// re-parse.
+    pub fn evaluate(&self) -> Result<AbstLiteralValue, VhdlError> {
+        match self {
+            Self::Decimal(s) => Self::eval_numeral(s, 10),
+            Self::Based(s) => {
+                let delim_pos = s.find(|c| c == char_set::HASH || c == char_set::COLON);
+                let delim_pos = delim_pos.ok_or_else(|| {
+                    VhdlError::Any(format!("malformed based literal '{}'", s))
+                })?;
+                let base = interpret_integer(&s[..delim_pos]);
+                if base < 2 || base > 16 {
+                    return Err(VhdlError::Any(String::from(
+                        "based literal must have base of at least 2 and at most 16",
+                    )));
+                }
+                Self::eval_numeral(&s[delim_pos + 1..], base)
+            }
+        }
+    }
+
+    /// Evaluates a numeral of the form `digits [ . digits ] [ E [ + | - ] digits ]`
+    /// in `base`; for [Self::Based], `numeral` still carries its own
+    /// trailing `#`/`:` delimiter, which is stripped off here.
+    fn eval_numeral(numeral: &str, base: usize) -> Result<AbstLiteralValue, VhdlError> {
+        // an exponent can only follow the closing `#`/`:` of a based
+        // literal, since `E` is also an extended digit in bases above 14
+        let exp_from = numeral
+            .rfind(|c| c == char_set::HASH || c == char_set::COLON)
+            .map_or(0, |i| i + 1);
+        let (mantissa, exponent) = match numeral[exp_from..].find(|c| c == 'e' || c == 'E') {
+            Some(i) => (&numeral[..exp_from + i], Some(&numeral[exp_from + i + 1..])),
+            None => (numeral, None),
+        };
+        let mantissa = mantissa.trim_end_matches(|c| c == char_set::HASH || c == char_set::COLON);
+
+        let (int_digits, frac_digits) = match mantissa.split_once(char_set::DOT) {
+            Some((i, f)) => (i, Some(f)),
+            None => (mantissa, None),
+        };
+
+        let exp_value: i32 = match exponent {
+            Some(e) => {
+                let (sign, digits) = match e.strip_prefix(char_set::PLUS) {
+                    Some(d) => (1, d),
+                    None => match e.strip_prefix(char_set::DASH) {
+                        Some(d) => (-1, d),
+                        None => (1, e),
+                    },
+                };
+                sign * interpret_integer(digits) as i32
+            }
+            None => 0,
+        };
+
+        // a pure integer: no fraction, and the exponent (if any) only scales up
+        if frac_digits.is_none() && exp_value >= 0 {
+            let mut value: u128 = 0;
+            for c in int_digits.chars().filter(|c| c != &char_set::UNDERLINE) {
+                let digit = Self::digit_value(c, base)?;
+                value = value
+                    .checked_mul(base as u128)
+                    .and_then(|v| v.checked_add(digit as u128))
+                    .ok_or_else(|| {
+                        VhdlError::Any(format!("literal '{}' overflows", numeral))
+                    })?;
+            }
+            for _ in 0..exp_value {
+                value = value.checked_mul(base as u128).ok_or_else(|| {
+                    VhdlError::Any(format!("literal '{}' overflows", numeral))
+                })?;
+            }
+            return Ok(AbstLiteralValue::Integer(value));
+        }
+
+        // a real: accumulate the integer part, then the fraction, then scale by the exponent
+        let mut value = 0f64;
+        for c in int_digits.chars().filter(|c| c != &char_set::UNDERLINE) {
+            value = value * base as f64 + Self::digit_value(c, base)? as f64;
+        }
+        if let Some(frac_digits) = frac_digits {
+            let mut scale = 1f64 / base as f64;
+            for c in frac_digits.chars().filter(|c| c != &char_set::UNDERLINE) {
+                value += Self::digit_value(c, base)? as f64 * scale;
+                scale /= base as f64;
+            }
+        }
+        value *= (base as f64).powi(exp_value);
+        Ok(AbstLiteralValue::Real(value))
+    }
+
+    /// Interprets `c` as an extended digit under `base` (2 through 16).
+    fn digit_value(c: char, base: usize) -> Result<u32, VhdlError> {
+        c.to_digit(base as u32)
+            .ok_or_else(|| VhdlError::Invalid(c.to_string()))
+    }
+}
+
 impl ToColor for Character {
     fn to_color(&self) -> ColoredString {
         let crayon = highlight::CHARS;
@@ -200,7 +536,7 @@ pub mod based_integer {
 }
 
 /// Set: B | O | X | UB | UO | UX | SB | SO | SX | D
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum BaseSpec {
     B,
     O,
@@ -248,4 +584,28 @@ impl BaseSpec {
             Self::D => "d",
         }
     }
+
+    /// The numeric radix this base specifier's digits are read in, or
+    /// `None` for [Self::D], which reads its body as a single base-10
+    /// integer rather than per-digit.
+    pub fn radix(&self) -> Option<u32> {
+        match self {
+            Self::B | Self::UB | Self::SB => Some(2),
+            Self::O | Self::UO | Self::SO => Some(8),
+            Self::X | Self::UX | Self::SX => Some(16),
+            Self::D => None,
+        }
+    }
+
+    /// Checks whether `c` is legal inside this base specifier's quoted
+    /// body: a digit of the right radix, or — for every specifier except
+    /// [Self::D] — one of the VHDL-2008 extended meta-values (see
+    /// [StdLogic]), since a decimal (`D`) literal has no notion of an
+    /// unknown/don't-care bit.
+    pub fn validate_digit(&self, c: char) -> bool {
+        match self.radix() {
+            Some(radix) => c.to_digit(radix).is_some() || StdLogic::from_char(c).is_some(),
+            None => c.is_ascii_digit(),
+        }
+    }
 }
@@ -6,11 +6,14 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 pub mod comment;
+pub mod confusable;
 pub mod delimiter;
+pub mod diagnostic;
 
 pub mod identifier;
 pub mod keyword;
 pub mod literal;
+pub mod raw;
 pub mod tokenizer;
 use super::highlight::*;
 
@@ -19,6 +22,7 @@ use tokenizer::*;
 
 pub type Identifier = identifier::Identifier;
 pub type Comment = comment::Comment;
+pub type CommentDirective = comment::CommentDirective;
 pub type Keyword = keyword::Keyword;
 pub type Delimiter = delimiter::Delimiter;
 pub type VhdlTokenizer = tokenizer::VhdlTokenizer;
@@ -38,6 +42,11 @@ pub enum VhdlToken {
     BitStrLiteral(BitStrLiteral), // (String)
     Keyword(Keyword),
     Delimiter(Delimiter),
+    /// A run of source text that failed to lex into any other token, paired
+    /// with the error that was raised while trying. Only ever produced by
+    /// [VhdlTokenizer::tokenize_lossy]; the strict [VhdlTokenizer::tokenize]
+    /// path never constructs this variant, it returns the error instead.
+    Invalid(String, VhdlError),
     EOF,
 }
 
@@ -52,6 +61,7 @@ impl ToColor for VhdlToken {
             Self::BitStrLiteral(b) => b.to_color(),
             Self::Keyword(k) => k.to_color(),
             Self::Delimiter(d) => d.to_color(),
+            Self::Invalid(s, _) => color(s, INVALID),
             Self::EOF => String::new().normal(),
         }
     }
@@ -71,6 +81,7 @@ impl Display for VhdlToken {
                 Self::BitStrLiteral(b) => b.to_string(),
                 Self::Keyword(kw) => kw.to_string(),
                 Self::Delimiter(d) => d.to_string(),
+                Self::Invalid(s, _) => s.clone(),
                 Self::EOF => String::new(),
             }
         )
@@ -236,7 +247,7 @@ impl VhdlToken {
                     }
                 }
                 // verify valid base specifier
-                BaseSpec::from_str(&base_spec)?;
+                let base_spec_kind = BaseSpec::from_str(&base_spec)?;
                 // force double quote to be next
                 if train.peek().is_none() || train.peek().unwrap() != &char_set::DOUBLE_QUOTE {
                     return Err(VhdlError::Any(String::from(
@@ -248,7 +259,7 @@ impl VhdlToken {
                 // append first double quote " char
                 number.push(train.consume().unwrap());
                 // complete tokenizing the bit string literal
-                return Ok(Self::consume_bit_str_literal(train, number)?);
+                return Ok(Self::consume_bit_str_literal(train, number, base_spec_kind)?);
             }
             // gather exponent
             if c == &'e' || c == &'E' {
@@ -280,10 +291,10 @@ impl VhdlToken {
                 if let Some(c) = train.peek() {
                     if c == &char_set::DOUBLE_QUOTE {
                         // verify valid base specifier
-                        BaseSpec::from_str(&word)?;
+                        let base_spec_kind = BaseSpec::from_str(&word)?;
                         // add the opening '"' character to the literal
                         word.push(train.consume().unwrap());
-                        return Ok(Self::consume_bit_str_literal(train, word)?);
+                        return Ok(Self::consume_bit_str_literal(train, word, base_spec_kind)?);
                     }
                 }
                 Ok(VhdlToken::Identifier(Identifier::Basic(word)))
@@ -294,22 +305,31 @@ impl VhdlToken {
     /// Captures the remaining characters for a bit string literal.
     ///
     /// Assumes the integer, base_specifier, and first " char are already consumed
-    /// and moved as `s0`.  Rules taken from VHDL-2019 LRM p177 due to backward-compatible additions. Note
+    /// and moved as `s0`, with `base_spec` the already-parsed meaning of that
+    /// base specifier.  Rules taken from VHDL-2019 LRM p177 due to backward-compatible additions. Note
     /// that a bit string literal is allowed to have no characters within the " ".
     /// - bit_string_literal ::= \[ integer ] base_specifier " \[ bit_value ] "
     /// - bit_value ::= graphic_character { [ underline ] graphic_character }
+    ///
+    /// Each digit of `bit_value` is checked against `base_spec` as it's
+    /// gathered (see [BaseSpec::validate_digit]), so an illegal digit is
+    /// rejected here at lex time instead of silently tokenizing and only
+    /// failing later when [BitStrLiteral::eval] is called.
     pub fn consume_bit_str_literal(
         train: &mut TrainCar<impl Iterator<Item = char>>,
         s0: String,
+        base_spec: BaseSpec,
     ) -> Result<VhdlToken, VhdlError> {
         let mut literal = s0;
         // consume bit_value (all graphic characters except the double quote " char)
-        let bit_value =
-            Self::consume_value_pattern(train, None, char_set::is_graphic_and_not_double_quote)?;
+        let bit_value = Self::consume_value_pattern(train, None, |c| {
+            char_set::is_graphic_and_not_double_quote(c)
+                && (*c == char_set::UNDERLINE || base_spec.validate_digit(*c))
+        })?;
         // verify the next character is the closing double quote " char
         if train.peek().is_none() || train.peek().unwrap() != &char_set::DOUBLE_QUOTE {
             return Err(VhdlError::Any(String::from(
-                "expecting closing double quote for bit string literal",
+                "expecting closing double quote for bit string literal, or an illegal digit for the chosen base",
             )));
         }
         literal.push_str(&bit_value);
@@ -420,7 +440,7 @@ impl VhdlToken {
                 note.push(c);
             }
         }
-        Ok(VhdlToken::Comment(Comment::Single(note)))
+        Ok(VhdlToken::Comment(Comment::classify(note)))
     }
 
     /// Walks through the possible interpretations for capturing a VHDL delimiter.
@@ -495,7 +515,7 @@ impl VhdlToken {
     fn consume_value_pattern(
         train: &mut TrainCar<impl Iterator<Item = char>>,
         c0: Option<char>,
-        eval: fn(&char) -> bool,
+        eval: impl Fn(&char) -> bool,
     ) -> Result<String, VhdlError> {
         let mut car = if let Some(c) = c0 {
             String::from(c)
@@ -623,6 +643,13 @@ impl VhdlToken {
         }
     }
 
+    pub fn is_invalid(&self) -> bool {
+        match self {
+            VhdlToken::Invalid(..) => true,
+            _ => false,
+        }
+    }
+
     /// Accesses the underlying `Identifier`, if one exists.
     pub fn as_identifier(&self) -> Option<&Identifier> {
         match self {
@@ -645,6 +672,33 @@ impl VhdlToken {
             _ => None,
         }
     }
+
+    /// Returns this token's attached documentation text, if it carries a
+    /// `--!` doc comment. Lets a future `orbit` documentation generator
+    /// pull entity/port descriptions straight from the token stream
+    /// without every caller re-matching on `Comment::Doc` itself.
+    pub fn as_doc_text(&self) -> Option<&str> {
+        self.as_comment().and_then(|c| c.doc_text())
+    }
+
+    /// Returns the synthesis/tool directive this token carries, if its
+    /// comment was recognized as one (see [Comment::classify]).
+    pub fn as_directive(&self) -> Option<&CommentDirective> {
+        self.as_comment().and_then(|c| c.directive())
+    }
+
+    /// Evaluates this token's literal into a concrete [literal::LiteralValue],
+    /// for downstream consumers (generic defaults, constant folding,
+    /// bit-width checks) that need an actual value rather than the raw
+    /// source text. Only the literal-bearing variants (`AbstLiteral`,
+    /// `BitStrLiteral`) evaluate to something.
+    pub fn eval(&self) -> Result<literal::LiteralValue, VhdlError> {
+        match self {
+            Self::AbstLiteral(a) => a.evaluate().and_then(literal::LiteralValue::try_from),
+            Self::BitStrLiteral(b) => Ok(literal::LiteralValue::BitVec(b.eval()?)),
+            _ => Err(VhdlError::Any(format!("'{}' is not a literal", self))),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -697,6 +751,107 @@ mod test {
         literal::interpret_integer(&contents);
     }
 
+    #[test]
+    fn eval_decimal_literal() {
+        use literal::AbstLiteralValue;
+
+        assert_eq!(
+            AbstLiteral::Decimal("32_000".to_owned()).evaluate(),
+            Ok(AbstLiteralValue::Integer(32_000))
+        );
+        assert_eq!(
+            AbstLiteral::Decimal("6.023E+3".to_owned()).evaluate(),
+            Ok(AbstLiteralValue::Real(6023.0))
+        );
+    }
+
+    #[test]
+    fn eval_based_literal() {
+        use literal::AbstLiteralValue;
+
+        // 16#0FF# == 255
+        assert_eq!(
+            AbstLiteral::Based("016#0FF#".to_owned()).evaluate(),
+            Ok(AbstLiteralValue::Integer(255))
+        );
+        // 2#1.1#E1 == (1 + 1/2) * 2^1 == 3.0
+        assert_eq!(
+            AbstLiteral::Based("2#1.1#E1".to_owned()).evaluate(),
+            Ok(AbstLiteralValue::Real(3.0))
+        );
+    }
+
+    #[test]
+    fn eval_abst_literal_overflow() {
+        // u128::MAX is 340282366920938463463374607431768211455; one more digit overflows
+        let huge = AbstLiteral::Decimal("999999999999999999999999999999999999999".to_owned());
+        assert_eq!(huge.evaluate().is_err(), true);
+    }
+
+    #[test]
+    fn bit_str_literal_eval_with_meta_values() {
+        use literal::StdLogic;
+
+        // a plain hex literal evaluates to '0'/'1' StdLogic digits
+        let bits = BitStrLiteral("x\"F\"".to_owned()).eval().unwrap();
+        assert_eq!(
+            bits,
+            vec![StdLogic::One, StdLogic::One, StdLogic::One, StdLogic::One]
+        );
+
+        // a meta-value digit replicates across its whole digit width
+        let bits = BitStrLiteral("sx\"F-\"".to_owned()).eval().unwrap();
+        assert_eq!(
+            bits,
+            vec![
+                StdLogic::One,
+                StdLogic::One,
+                StdLogic::One,
+                StdLogic::One,
+                StdLogic::DontCare,
+                StdLogic::DontCare,
+                StdLogic::DontCare,
+                StdLogic::DontCare,
+            ]
+        );
+
+        // a 'D' (decimal) literal has no use for meta-values and rejects them
+        let bits = BitStrLiteral("d\"X\"".to_owned()).eval();
+        assert_eq!(bits.is_err(), true);
+    }
+
+    #[test]
+    fn vhdl_token_eval_literal() {
+        use literal::{LiteralValue, StdLogic};
+
+        // an `AbstLiteral` dispatches to an integer `LiteralValue`
+        let tk = VhdlToken::AbstLiteral(AbstLiteral::Decimal("42".to_owned()));
+        assert_eq!(tk.eval().unwrap(), LiteralValue::Integer(42));
+
+        // a `BitStrLiteral` dispatches to a `BitVec` `LiteralValue`
+        let tk = VhdlToken::BitStrLiteral(BitStrLiteral("x\"F\"".to_owned()));
+        assert_eq!(
+            tk.eval().unwrap(),
+            LiteralValue::BitVec(vec![
+                StdLogic::One,
+                StdLogic::One,
+                StdLogic::One,
+                StdLogic::One
+            ])
+        );
+
+        // a non-literal token has nothing to evaluate to
+        let tk = VhdlToken::Keyword(Keyword::Entity);
+        assert_eq!(tk.eval().is_err(), true);
+
+        // an integer literal too large for an i128 fails the bridging
+        // conversion from `AbstLiteralValue` to `LiteralValue`
+        let tk = VhdlToken::AbstLiteral(AbstLiteral::Decimal(
+            "999999999999999999999999999999999999999".to_owned(),
+        ));
+        assert_eq!(tk.eval().is_err(), true);
+    }
+
     #[test]
     fn single_quote_as_delimiter() {
         let contents = "\
@@ -789,6 +944,72 @@ foo <= std_logic_vector'('a','b','c');";
         assert_eq!(tc.locate(), &Position::place(1, 8));
     }
 
+    #[test]
+    fn lex_based_literal_with_fraction_and_exponent() {
+        // base#digits.digits#Eexp, with underscores preserved throughout
+        let contents = "2#001_1100.001#E14;";
+        let mut tc = TrainCar::new(contents.chars());
+        let c0 = tc.consume().unwrap();
+        assert_eq!(
+            VhdlToken::consume_numeric(&mut tc, c0).unwrap(),
+            VhdlToken::AbstLiteral(AbstLiteral::Based("2#001_1100.001#E14".to_owned()))
+        );
+        assert_eq!(tc.peekable().clone().collect::<String>(), ";");
+    }
+
+    #[test]
+    fn lex_integer_based_literal() {
+        let contents = "2#10101#;";
+        let mut tc = TrainCar::new(contents.chars());
+        let c0 = tc.consume().unwrap();
+        assert_eq!(
+            VhdlToken::consume_numeric(&mut tc, c0).unwrap(),
+            VhdlToken::AbstLiteral(AbstLiteral::Based("2#10101#".to_owned()))
+        );
+        assert_eq!(
+            AbstLiteral::Based("2#10101#".to_owned()).evaluate().unwrap(),
+            literal::AbstLiteralValue::Integer(21)
+        );
+    }
+
+    #[test]
+    fn lex_bit_str_literal_with_size_prefix_and_format_specifier() {
+        // [integer] base_specifier "value": a pre-size on an extended hex specifier
+        let contents = "8sx\"1F\";";
+        let mut tc = TrainCar::new(contents.chars());
+        let c0 = tc.consume().unwrap();
+        assert_eq!(
+            VhdlToken::consume_numeric(&mut tc, c0).unwrap(),
+            VhdlToken::BitStrLiteral(BitStrLiteral("8sx\"1F\"".to_owned()))
+        );
+        assert_eq!(tc.peekable().clone().collect::<String>(), ";");
+    }
+
+    #[test]
+    fn lex_bit_str_literal_rejects_illegal_digit_for_base() {
+        // 'g' is not a legal hex digit, so this should fail at lex time
+        // rather than silently tokenizing and only failing on eval()
+        let contents = "x\"1G\";";
+        let mut tc = TrainCar::new(contents.chars());
+        let c0 = tc.consume().unwrap();
+        assert_eq!(VhdlToken::consume_word(&mut tc, c0).is_err(), true);
+
+        // decimal bit strings don't accept the extended meta-values either
+        let contents = "d\"1X\";";
+        let mut tc = TrainCar::new(contents.chars());
+        let c0 = tc.consume().unwrap();
+        assert_eq!(VhdlToken::consume_word(&mut tc, c0).is_err(), true);
+
+        // but a legal hex digit and a legal meta-value both lex cleanly
+        let contents = "x\"1F-\";";
+        let mut tc = TrainCar::new(contents.chars());
+        let c0 = tc.consume().unwrap();
+        assert_eq!(
+            VhdlToken::consume_word(&mut tc, c0).unwrap(),
+            VhdlToken::BitStrLiteral(BitStrLiteral("x\"1F-\"".to_owned()))
+        );
+    }
+
     #[test]
     fn lex_numeric() {
         let contents = "32)";
@@ -967,6 +1188,55 @@ entity fa is end entity;";
         assert_eq!(tc.locate(), &Position::place(2, 0));
     }
 
+    #[test]
+    fn classify_doc_comment() {
+        let contents = "\
+--!Computes the parity of `data`.";
+        let mut tc = TrainCar::new(contents.chars());
+        tc.consume(); // already determined first dash
+        let tk = VhdlToken::consume_comment(&mut tc).unwrap();
+        assert_eq!(
+            tk,
+            VhdlToken::Comment(Comment::Doc(
+                "!Computes the parity of `data`.".to_owned()
+            ))
+        );
+        assert_eq!(tk.as_doc_text(), Some("Computes the parity of `data`."));
+        assert_eq!(tk.as_directive(), None);
+    }
+
+    #[test]
+    fn classify_synthesis_directive() {
+        let contents = "\
+-- synthesis translate_off";
+        let mut tc = TrainCar::new(contents.chars());
+        tc.consume();
+        let tk = VhdlToken::consume_comment(&mut tc).unwrap();
+        assert_eq!(
+            tk,
+            VhdlToken::Comment(Comment::Directive(
+                " synthesis translate_off".to_owned(),
+                CommentDirective::TranslateOff
+            ))
+        );
+        assert_eq!(tk.as_directive(), Some(&CommentDirective::TranslateOff));
+        assert_eq!(tk.as_doc_text(), None);
+
+        let contents = "\
+-- vhdl_comp_off";
+        let mut tc = TrainCar::new(contents.chars());
+        tc.consume();
+        let tk = VhdlToken::consume_comment(&mut tc).unwrap();
+        assert_eq!(tk.as_directive(), Some(&CommentDirective::VhdlCompOff));
+
+        let contents = "\
+-- pragma translate_off";
+        let mut tc = TrainCar::new(contents.chars());
+        tc.consume();
+        let tk = VhdlToken::consume_comment(&mut tc).unwrap();
+        assert_eq!(tk.as_directive(), Some(&CommentDirective::Pragma));
+    }
+
     #[test]
     fn lex_delim_comment() {
         let contents = "\
@@ -1352,6 +1622,48 @@ entity fa is end entity;";
         );
     }
 
+    #[test]
+    fn token_span_covers_full_word() {
+        let s = "entity fa is";
+        let tokens = VhdlTokenizer::tokenize(s)
+            .into_iter()
+            .map(|f| f.unwrap())
+            .collect::<Vec<Token<VhdlToken>>>();
+        // "entity" spans columns 1 through 6 (its start and last character),
+        // not just the single point its `locate()` marks
+        let entity_tk = &tokens[0];
+        assert_eq!(entity_tk.locate(), &Position::place(1, 1));
+        assert_eq!(entity_tk.end_position(), &Position::place(1, 6));
+        // "fa" spans columns 8 through 9
+        let fa_tk = &tokens[1];
+        assert_eq!(fa_tk.locate(), &Position::place(1, 8));
+        assert_eq!(fa_tk.end_position(), &Position::place(1, 9));
+
+        // `Token::span` bundles the same two positions into one value
+        let span = entity_tk.span();
+        assert_eq!(span.start, Position::place(1, 1));
+        assert_eq!(span.end, Position::place(1, 6));
+    }
+
+    #[test]
+    fn multi_line_delim_comment_span_ends_on_its_closing_line() {
+        let s = "/* line one\nline two */ entity";
+        let tokens = VhdlTokenizer::tokenize(s)
+            .into_iter()
+            .map(|f| f.unwrap())
+            .collect::<Vec<Token<VhdlToken>>>();
+
+        let comment_tk = &tokens[0];
+        assert_eq!(comment_tk.locate(), &Position::place(1, 1));
+        // the comment closes on line 2, not where it started on line 1
+        assert_eq!(comment_tk.end_position().line(), 2);
+        assert_eq!(comment_tk.end_position(), &Position::place(2, 11));
+
+        // the token after the comment resumes counting from line 2
+        let entity_tk = &tokens[1];
+        assert_eq!(entity_tk.locate().line(), 2);
+    }
+
     #[test]
     fn lex_delimiter_single() {
         let contents = "&";
@@ -1573,4 +1885,78 @@ end architecture rtl; /* long comment */";
         println!("{:?}", vhdl);
         panic!("manually inspect token list")
     }
+
+    #[test]
+    fn tokenize_lossy_recovers_after_bad_token() {
+        // `z"1010"` has an invalid bit string base specifier; tokenize_lossy
+        // should still find the valid tokens on either side of it
+        let contents = "a <= z\"1010\"; b <= '1';";
+        let (tokens, errors) = VhdlTokenizer::tokenize_lossy(&contents);
+
+        assert_eq!(errors.is_empty(), false);
+        assert_eq!(
+            tokens
+                .iter()
+                .any(|t| t.as_ref().is_invalid()),
+            true
+        );
+        // the tokens after the bad one are still recovered
+        assert_eq!(
+            tokens
+                .iter()
+                .any(|t| t.as_ref() == &VhdlToken::Identifier(Identifier::Basic("b".to_owned()))),
+            true
+        );
+        assert_eq!(tokens.last().unwrap().as_ref(), &VhdlToken::EOF);
+    }
+
+    #[test]
+    fn tokenize_lossy_resyncs_unterminated_comment_to_eof() {
+        // a delimited comment with no closing "*/" swallows the rest of the
+        // file looking for one (consume_delim_comment's own behavior), so
+        // there is nothing left afterward regardless of resync strategy;
+        // this just confirms the comment-aware path is exercised cleanly
+        let contents = "a <= '1'; /* oops forgot the close";
+        let (tokens, errors) = VhdlTokenizer::tokenize_lossy(&contents);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(tokens.last().unwrap().as_ref(), &VhdlToken::EOF);
+    }
+
+    #[test]
+    fn tokenize_lossy_resyncs_unterminated_string_past_newline() {
+        // VHDL string literals can't span a line, so the bare newline here
+        // (not itself a graphic character) ends the failed literal early,
+        // leaving the rest of the file for resync to recover
+        let contents = "a <= \"oops forgot the close\nb <= '1';";
+        let (tokens, errors) = VhdlTokenizer::tokenize_lossy(&contents);
+        assert_eq!(errors.is_empty(), false);
+        assert_eq!(
+            tokens
+                .iter()
+                .any(|t| t.as_ref() == &VhdlToken::Identifier(Identifier::Basic("b".to_owned()))),
+            true
+        );
+        assert_eq!(tokens.last().unwrap().as_ref(), &VhdlToken::EOF);
+    }
+
+    #[test]
+    fn tokenize_with_diagnostics_collects_every_problem() {
+        use diagnostic::LexMessage;
+
+        // 99#FF# declares an out-of-range base (must be 2..=16); the
+        // comment afterward never closes
+        let contents = "a <= 99#FF#; /* never closes";
+        let (tokens, logger) = VhdlTokenizer::tokenize_with_diagnostics(&contents);
+
+        assert_eq!(logger.is_empty(), false);
+        assert!(logger
+            .logs()
+            .iter()
+            .any(|log| matches!(log.message, LexMessage::InvalidBasedLiteral { .. })));
+        assert!(logger
+            .logs()
+            .iter()
+            .any(|log| log.message == LexMessage::UnclosedDelimitedComment));
+        assert_eq!(tokens.last().unwrap().as_ref(), &VhdlToken::EOF);
+    }
 }
@@ -0,0 +1,281 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::super::super::lexer::ByteCursor;
+use super::keyword::Keyword;
+
+/// What a [RawToken] looks like, lexically — no [super::super::super::lexer::Position]
+/// and no [super::super::error::VhdlError]. A malformed token (an unclosed
+/// string, an unclosed comment) is still emitted with its full consumed
+/// length; it just carries `terminated: false` instead of the caller
+/// having to interpret an `Err`.
+///
+/// This only classifies *shape*. It does not validate a bit-string
+/// literal's digits against its base, or fully parse a based literal's
+/// exponent — that level of detail belongs to the richer pass over these
+/// raw tokens (see [super::tokenizer::VhdlTokenizer] and, for
+/// spec-accurate literal lexing specifically, the dedicated literal
+/// collectors in [super::mod].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RawVhdlKind {
+    Whitespace,
+    /// A run of letters/digits/underscores that isn't a recognized
+    /// keyword.
+    Identifier,
+    /// A run of letters/digits/underscores that matched [Keyword::match_keyword].
+    Keyword,
+    /// A decimal/based/bit-string numeral. As noted above, this pass only
+    /// recognizes the run as "numeric-shaped", it does not validate it.
+    AbstLiteral,
+    /// A `'x'` character literal.
+    CharLiteral { terminated: bool },
+    /// A `"..."` string literal.
+    StrLiteral { terminated: bool },
+    /// A single-line `--` comment, running to end-of-line or end-of-file.
+    LineComment,
+    /// A `/* ... */` comment.
+    DelimComment { terminated: bool },
+    /// Punctuation not otherwise classified above; length disambiguation
+    /// between `<`, `<=`, and `<>`-style multi-character delimiters is
+    /// left to [super::mod::VhdlToken::collect_delimiter], so this always
+    /// has length 1.
+    Delimiter,
+    /// A single byte/char this pass doesn't recognize at all.
+    Unknown,
+}
+
+/// A single raw lexical run: `kind`, paired with its length in bytes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RawToken {
+    pub kind: RawVhdlKind,
+    pub len: usize,
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic()
+}
+
+fn is_ident_continue(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn is_numeral_continue(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'#' | b':' | b'+' | b'-')
+}
+
+/// Pure-`&str`, span-free, error-free lexing of `s` into a flat sequence
+/// of [RawToken]s: a layer that an incremental re-lexer or a syntax
+/// highlighter can run over an edited region without dragging in
+/// [super::super::super::lexer::Position] bookkeeping or a
+/// [super::super::error::VhdlError] per malformed token.
+///
+/// Every byte of `s` is accounted for by exactly one token (including
+/// whitespace), so `tokens.iter().map(|t| t.len).sum::<usize>() == s.len()`
+/// always holds.
+pub fn lex_raw(s: &str) -> Vec<RawToken> {
+    let mut cursor = ByteCursor::new(s);
+    let mut tokens = Vec::new();
+
+    while let Some(b0) = cursor.peek_byte() {
+        let start = cursor.byte_offset();
+        let kind = if b0.is_ascii_whitespace() {
+            cursor.take_while(|b| b.is_ascii_whitespace());
+            RawVhdlKind::Whitespace
+        } else if is_ident_start(b0) {
+            let text = cursor.take_while(is_ident_continue);
+            match Keyword::match_keyword(text) {
+                Some(_) => RawVhdlKind::Keyword,
+                None => RawVhdlKind::Identifier,
+            }
+        } else if b0.is_ascii_digit() {
+            cursor.take_while(is_numeral_continue);
+            RawVhdlKind::AbstLiteral
+        } else if b0 == b'"' {
+            cursor.bump();
+            let terminated = scan_to_closing(&mut cursor, '"');
+            RawVhdlKind::StrLiteral { terminated }
+        } else if b0 == b'\'' && is_char_literal_ahead(&cursor) {
+            cursor.bump();
+            cursor.bump();
+            cursor.bump();
+            RawVhdlKind::CharLiteral { terminated: true }
+        } else if b0 == b'-' && cursor.rest().as_bytes().get(1) == Some(&b'-') {
+            cursor.bump();
+            cursor.bump();
+            cursor.take_while(|b| b != b'\n');
+            RawVhdlKind::LineComment
+        } else if b0 == b'/' && cursor.rest().as_bytes().get(1) == Some(&b'*') {
+            cursor.bump();
+            cursor.bump();
+            let terminated = scan_to_delim_comment_close(&mut cursor);
+            RawVhdlKind::DelimComment { terminated }
+        } else if char_is_vhdl_special(b0) {
+            cursor.bump();
+            RawVhdlKind::Delimiter
+        } else {
+            cursor.bump();
+            RawVhdlKind::Unknown
+        };
+
+        tokens.push(RawToken {
+            kind,
+            len: cursor.byte_offset() - start,
+        });
+    }
+
+    tokens
+}
+
+/// A crude, ASCII-only punctuation gate for [lex_raw]'s delimiter
+/// fallback — the real, exhaustive set lives in
+/// [super::mod::char_set::is_special], but that takes a `char`, not a
+/// `u8`, and this pass never needs more than "is this punctuation at
+/// all".
+fn char_is_vhdl_special(b: u8) -> bool {
+    matches!(
+        b,
+        b'"' | b'#'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b'-'
+            | b'.'
+            | b'/'
+            | b':'
+            | b';'
+            | b'<'
+            | b'='
+            | b'>'
+            | b'?'
+            | b'@'
+            | b'['
+            | b']'
+            | b'_'
+            | b'`'
+            | b'|'
+    )
+}
+
+/// Checks whether `cursor` is positioned at a `'x'` single-character
+/// literal (as opposed to a lone `'` delimiter used for an attribute name
+/// like `signal'range`): true when the byte two positions ahead closes
+/// the quote.
+fn is_char_literal_ahead(cursor: &ByteCursor) -> bool {
+    let rest = cursor.rest().as_bytes();
+    rest.len() >= 3 && rest[0] == b'\'' && rest[2] == b'\''
+}
+
+/// Scans forward until `close` is found (consuming it) or the input runs
+/// out; returns whether the closing character was actually found.
+fn scan_to_closing(cursor: &mut ByteCursor, close: char) -> bool {
+    while let Some(c) = cursor.peek_char() {
+        if c == close {
+            cursor.bump();
+            return true;
+        }
+        if c == '\n' {
+            // VHDL string/char literals can't span a line
+            return false;
+        }
+        cursor.bump();
+    }
+    false
+}
+
+/// Scans forward until `*/` is found (consuming it) or the input runs
+/// out; returns whether the closing delimiter was actually found.
+fn scan_to_delim_comment_close(cursor: &mut ByteCursor) -> bool {
+    while let Some(c) = cursor.bump() {
+        if c == '*' && cursor.peek_char() == Some('/') {
+            cursor.bump();
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kinds(s: &str) -> Vec<RawVhdlKind> {
+        lex_raw(s).into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn every_byte_is_accounted_for() {
+        let s = "entity fa is\nend entity fa;";
+        let total: usize = lex_raw(s).into_iter().map(|t| t.len).sum();
+        assert_eq!(total, s.len());
+    }
+
+    #[test]
+    fn classifies_identifiers_and_keywords() {
+        assert_eq!(
+            kinds("entity fa"),
+            vec![
+                RawVhdlKind::Keyword,
+                RawVhdlKind::Whitespace,
+                RawVhdlKind::Identifier,
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_unterminated_string_instead_of_erroring() {
+        let tokens = lex_raw("\"oops");
+        assert_eq!(
+            tokens[0].kind,
+            RawVhdlKind::StrLiteral { terminated: false }
+        );
+
+        let tokens = lex_raw("\"ok\"");
+        assert_eq!(tokens[0].kind, RawVhdlKind::StrLiteral { terminated: true });
+    }
+
+    #[test]
+    fn flags_unterminated_delimited_comment_instead_of_erroring() {
+        let tokens = lex_raw("/* never closes");
+        assert_eq!(
+            tokens[0].kind,
+            RawVhdlKind::DelimComment { terminated: false }
+        );
+
+        let tokens = lex_raw("/* closes */");
+        assert_eq!(
+            tokens[0].kind,
+            RawVhdlKind::DelimComment { terminated: true }
+        );
+    }
+
+    #[test]
+    fn classifies_char_literal_vs_tick_delimiter() {
+        let tokens = lex_raw("'1'");
+        assert_eq!(tokens[0].kind, RawVhdlKind::CharLiteral { terminated: true });
+        assert_eq!(tokens[0].len, 3);
+
+        // a tick followed by `range` (as in `sig'range`) is a bare
+        // attribute tick, not a char literal
+        let tokens = lex_raw("'range");
+        assert_eq!(tokens[0].kind, RawVhdlKind::Delimiter);
+        assert_eq!(tokens[0].len, 1);
+    }
+}
@@ -129,6 +129,7 @@ use super::super::super::lexer::{Token, TokenError};
 
 use super::super::error::VhdlError;
 use super::super::token::VhdlToken;
+use super::diagnostic;
 use crate::core::lang::lexer::Tokenize;
 use std::str::FromStr;
 
@@ -240,24 +241,27 @@ impl Tokenize for VhdlTokenizer {
             if char_set::is_separator(&c) {
                 continue;
             }
+            // the position of `c`, the first character of the token about to
+            // be collected; paired with `train.locate()` right after the
+            // `consume_*` call returns to give each token its full span
             let tk_loc = train.locate().clone();
             // build a token
             tokens.push(if char_set::is_letter(&c) {
                 // collect general identifier
                 match Self::TokenType::consume_word(&mut train, c) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             } else if c == char_set::BACKSLASH {
                 // collect extended identifier
                 match Self::TokenType::consume_extended_identifier(&mut train) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             } else if c == char_set::DOUBLE_QUOTE {
                 // collect string literal
                 match Self::TokenType::consume_str_lit(&mut train) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             } else if c == char_set::SINGLE_QUOTE
@@ -273,13 +277,13 @@ impl Tokenize for VhdlTokenizer {
             {
                 // collect character literal
                 match Self::TokenType::consume_char_lit(&mut train) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             } else if char_set::is_digit(&c) {
                 // collect decimal literal (or bit string literal or based literal)
                 match Self::TokenType::consume_numeric(&mut train, c) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             } else if c == char_set::DASH
@@ -288,7 +292,7 @@ impl Tokenize for VhdlTokenizer {
             {
                 // collect a single-line comment
                 match Self::TokenType::consume_comment(&mut train) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             } else if c == char_set::FWDSLASH
@@ -297,7 +301,7 @@ impl Tokenize for VhdlTokenizer {
             {
                 // collect delimited (multi-line) comment
                 match Self::TokenType::consume_delim_comment(&mut train) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => {
                         let mut tk_loc = train.locate().clone();
                         tk_loc.next_col(); // +1 col for correct alignment
@@ -307,7 +311,7 @@ impl Tokenize for VhdlTokenizer {
             } else {
                 // collect delimiter
                 match Self::TokenType::collect_delimiter(&mut train, Some(c)) {
-                    Ok(tk) => Ok(Token::new(tk, tk_loc)),
+                    Ok(tk) => Ok(Token::new_spanned(tk, tk_loc, train.locate().clone())),
                     Err(e) => Err(TokenError::new(e, train.locate().clone())),
                 }
             });
@@ -319,3 +323,189 @@ impl Tokenize for VhdlTokenizer {
         tokens
     }
 }
+
+impl VhdlTokenizer {
+    /// Consumes characters from `train` until the next likely token boundary
+    /// (whitespace, or the start of what looks like a new token), returning
+    /// everything consumed.
+    ///
+    /// Used by [Self::tokenize_lossy] to resynchronize after a malformed run
+    /// of characters. This is a heuristic, not a precise replay of how far
+    /// the failing `consume_*` call itself advanced `train`: some of those
+    /// calls (an unterminated string literal, for instance) already consume
+    /// to end-of-file before returning their error, in which case there is
+    /// nothing left here to resynchronize over.
+    fn resync<T: Iterator<Item = char>>(train: &mut TrainCar<T>) -> String {
+        let mut extra = String::new();
+        while let Some(c) = train.peek() {
+            if char_set::is_separator(c)
+                || char_set::is_letter(c)
+                || char_set::is_digit(c)
+                || char_set::is_special(c)
+            {
+                break;
+            }
+            extra.push(train.consume().unwrap());
+        }
+        extra
+    }
+
+    /// Resynchronizes after an unterminated delimited (`/* ... */`) comment
+    /// by consuming through the next `*/`, or to end-of-file if the comment
+    /// is never closed.
+    fn resync_to_comment_close<T: Iterator<Item = char>>(train: &mut TrainCar<T>) -> String {
+        let mut extra = String::new();
+        while let Some(c) = train.consume() {
+            extra.push(c);
+            if c == char_set::STAR && train.peek() == Some(&char_set::FWDSLASH) {
+                extra.push(train.consume().unwrap());
+                break;
+            }
+        }
+        extra
+    }
+
+    /// Resynchronizes after an unterminated string or character literal by
+    /// skipping ahead to the next statement boundary (`;`, consumed as part
+    /// of the error span) or the next letter/digit/separator, whichever
+    /// comes first. Like [Self::resync], this stops short of consuming a
+    /// character that looks like the start of the next real token; the one
+    /// difference is that a `;` is treated as belonging to the broken
+    /// statement rather than left for the next token, since a statement
+    /// that never closed its quote has no valid terminator of its own.
+    fn resync_to_boundary<T: Iterator<Item = char>>(train: &mut TrainCar<T>) -> String {
+        let mut extra = String::new();
+        while let Some(c) = train.peek() {
+            let c = *c;
+            if c == ';' {
+                extra.push(train.consume().unwrap());
+                break;
+            }
+            if char_set::is_separator(&c)
+                || char_set::is_letter(&c)
+                || char_set::is_digit(&c)
+                || char_set::is_special(&c)
+            {
+                break;
+            }
+            extra.push(train.consume().unwrap());
+        }
+        extra
+    }
+
+    /// Tokenizes `s` the same way [Tokenize::tokenize] does, except a
+    /// malformed run of characters is captured as a [VhdlToken::Invalid]
+    /// token (see [Self::resync]) instead of aborting the token stream at
+    /// that point. Returns the full token stream alongside every error
+    /// encountered along the way, each paired with its source position.
+    ///
+    /// This mirrors the "lex pure text, never bail" approach used by tools
+    /// like rustc_lexer, so a caller (syntax highlighting, a future
+    /// language server) can still work with the rest of a source file that
+    /// has a mistake in it. [Tokenize::tokenize] (the strict path used for
+    /// planning and parsing) is unchanged.
+    ///
+    /// Resynchronization picks a strategy based on what kind of construct
+    /// was being lexed when it failed (see [Self::resync_to_comment_close],
+    /// [Self::resync_to_boundary]) rather than a single generic heuristic
+    /// for every error.
+    pub fn tokenize_lossy(s: &str) -> (Vec<Token<VhdlToken>>, Vec<TokenError<VhdlError>>) {
+        let mut train = TrainCar::new(s.chars());
+        let mut tokens: Vec<Token<VhdlToken>> = Vec::new();
+        let mut errors: Vec<TokenError<VhdlError>> = Vec::new();
+
+        while let Some(c) = train.consume() {
+            if char_set::is_separator(&c) {
+                continue;
+            }
+            let tk_loc = train.locate().clone();
+            let is_quoted_literal = c == char_set::DOUBLE_QUOTE
+                || (c == char_set::SINGLE_QUOTE
+                    && tokens.last().is_some()
+                    && tokens.last().unwrap().as_ref().is_delimiter());
+            let is_delim_comment = c == char_set::FWDSLASH
+                && train.peek().is_some()
+                && train.peek().unwrap() == &char_set::STAR;
+            let result = if char_set::is_letter(&c) {
+                VhdlToken::consume_word(&mut train, c)
+            } else if c == char_set::BACKSLASH {
+                VhdlToken::consume_extended_identifier(&mut train)
+            } else if c == char_set::DOUBLE_QUOTE {
+                VhdlToken::consume_str_lit(&mut train)
+            } else if is_quoted_literal {
+                VhdlToken::consume_char_lit(&mut train)
+            } else if char_set::is_digit(&c) {
+                VhdlToken::consume_numeric(&mut train, c)
+            } else if c == char_set::DASH
+                && train.peek().is_some()
+                && train.peek().unwrap() == &char_set::DASH
+            {
+                VhdlToken::consume_comment(&mut train)
+            } else if is_delim_comment {
+                VhdlToken::consume_delim_comment(&mut train)
+            } else {
+                VhdlToken::collect_delimiter(&mut train, Some(c))
+            };
+
+            match result {
+                Ok(tk) => tokens.push(Token::new_spanned(tk, tk_loc, train.locate().clone())),
+                Err(e) => {
+                    // pick a resynchronization strategy suited to what kind
+                    // of construct was being lexed, rather than always
+                    // stopping at the first generic-looking boundary
+                    let extra = if is_delim_comment {
+                        Self::resync_to_comment_close(&mut train)
+                    } else if is_quoted_literal {
+                        Self::resync_to_boundary(&mut train)
+                    } else {
+                        Self::resync(&mut train)
+                    };
+                    // consume_delim_comment already took the opening '*', so
+                    // it is put back to keep the "/*" shape of the failed text
+                    let invalid_text = if is_delim_comment {
+                        format!("{}{}{}", c, char_set::STAR, extra)
+                    } else {
+                        format!("{}{}", c, extra)
+                    };
+                    errors.push(TokenError::new(e.clone(), tk_loc.clone()));
+                    let end_loc = train.locate().clone();
+                    tokens.push(Token::new_spanned(
+                        VhdlToken::Invalid(invalid_text, e),
+                        tk_loc,
+                        end_loc,
+                    ));
+                }
+            }
+        }
+
+        let mut tk_loc = train.locate().clone();
+        tk_loc.next_col();
+        tokens.push(Token::new(VhdlToken::EOF, tk_loc));
+        (tokens, errors)
+    }
+
+    /// Tokenizes `s` the same way [Self::tokenize_lossy] does, reporting
+    /// every lexical mistake as a classified [diagnostic::Log] collected
+    /// into a [diagnostic::Logger] instead of a raw [TokenError]/[VhdlError]
+    /// pair, so a caller can print every problem in the file at once
+    /// without re-deriving what kind of mistake each one was.
+    ///
+    /// Built directly on top of [Self::tokenize_lossy] rather than a
+    /// parallel lexing pass: every [VhdlToken::Invalid] token it produces
+    /// already carries the raw text and [VhdlError] needed to classify it
+    /// (see [diagnostic::LexMessage::classify]).
+    pub fn tokenize_with_diagnostics(s: &str) -> (Vec<Token<VhdlToken>>, diagnostic::Logger) {
+        let (tokens, _) = Self::tokenize_lossy(s);
+        let mut logger = diagnostic::Logger::new();
+        for tk in &tokens {
+            if let VhdlToken::Invalid(text, err) = tk.as_type() {
+                logger.push(diagnostic::Log {
+                    message: diagnostic::LexMessage::classify(text, err),
+                    span: tk.span(),
+                    file: None,
+                });
+            }
+        }
+        (tokens, logger)
+    }
+}
@@ -3,7 +3,7 @@ use toml_edit::{Document, InlineTable, Formatted, Array};
 use crate::{util::{sha256::Sha256Hash, anyerror::{AnyError, Fault}}, core::{pkgid::PkgId, version::{Version, AnyVersion, self}, config::FromToml, manifest::IpManifest}};
 use crate::util::url::Url;
 
-use super::{ip::IpSpec, catalog::CacheSlot};
+use super::{ip::{Ip, IpSpec}, catalog::CacheSlot};
 
 type Module = (PkgId, AnyVersion);
 
@@ -84,7 +84,87 @@ impl LockFile {
 
     pub fn inner(&self) -> &Vec<LockEntry> {
         &self.0
-    } 
+    }
+
+    /// Recomputes the checksum for every `(name, version, root)` triple in
+    /// `installed` and compares it against the value recorded for that
+    /// entry in this lockfile, catching tampering or local corruption.
+    ///
+    /// An entry with no matching lockfile entry, or whose lockfile entry
+    /// has no recorded `sum`, is skipped rather than treated as a mismatch.
+    /// Every divergence is collected and returned together, rather than
+    /// failing fast on the first one, so a single `--locked` run can report
+    /// all corrupted ip all at once.
+    pub fn verify(&self, installed: &[(PkgId, Version, PathBuf)]) -> Result<(), VerificationError> {
+        let mut corrupt = Vec::new();
+        for (name, version, root) in installed {
+            let entry = match self.get(name, version) {
+                Some(e) => e,
+                None => continue,
+            };
+            let expected = match entry.get_sum() {
+                Some(s) => s,
+                None => continue,
+            };
+            let actual = Ip::compute_checksum(root);
+            if &actual != expected {
+                corrupt.push(Corruption {
+                    name: name.clone(),
+                    version: version.clone(),
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+        if corrupt.is_empty() {
+            Ok(())
+        } else {
+            Err(VerificationError(corrupt))
+        }
+    }
+}
+
+/// A single checksum mismatch detected by [LockFile::verify]: the ip named
+/// `name`@`version` no longer matches the checksum recorded for it in
+/// `Orbit.lock`.
+#[derive(Debug, PartialEq)]
+pub struct Corruption {
+    name: PkgId,
+    version: Version,
+    expected: String,
+    actual: String,
+}
+
+impl std::fmt::Display for Corruption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} checksum mismatch: expected {} but computed {}",
+            self.name, self.version, self.expected, self.actual
+        )
+    }
+}
+
+/// The set of [Corruption] detected by a single [LockFile::verify] pass.
+#[derive(Debug, PartialEq)]
+pub struct VerificationError(Vec<Corruption>);
+
+impl VerificationError {
+    pub fn inner(&self) -> &Vec<Corruption> {
+        &self.0
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "checksum verification failed for {} ip:", self.0.len())?;
+        for c in &self.0 {
+            writeln!(f, "  {}", c)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -29,6 +29,7 @@ use std::fmt::{self, Display};
 use std::path::PathBuf;
 use std::{collections::HashMap, str::FromStr};
 
+use super::fileset::Style;
 use super::ip::Ip;
 use super::lang::vhdl::token::identifier::Identifier;
 use super::lang::LangIdentifier;
@@ -196,6 +197,11 @@ pub struct Manifest {
         default
     )]
     dev_dependencies: Dependencies,
+    /// Named groups of glob patterns that tag which of this ip's HDL files
+    /// belong to which build scenario (see `--scenario` on `build`/`test`).
+    /// An ip that defines none opts all of its files into every scenario.
+    #[serde(skip_serializing_if = "map_is_empty", default)]
+    scenario: HashMap<String, Vec<Style>>,
 }
 
 pub trait FromFile: FromStr
@@ -292,6 +298,7 @@ impl Manifest {
             },
             dependencies: Dependencies::new(),
             dev_dependencies: Dependencies::new(),
+            scenario: HashMap::new(),
         }
     }
 
@@ -365,6 +372,11 @@ version = "0.1.0"
         &self.dev_dependencies
     }
 
+    /// Returns the named build-scenario fileset groups for this ip, if any.
+    pub fn get_scenarios(&self) -> &HashMap<String, Vec<Style>> {
+        &self.scenario
+    }
+
     pub fn is_deps_valid(&self) -> Result<(), AnyError> {
         for (key, _) in &self.dependencies {
             if let Some(_) = self.dev_dependencies.get(key) {
@@ -15,9 +15,12 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+pub mod alias;
 pub mod algo;
 pub mod blueprint;
+pub mod cache;
 pub mod catalog;
+pub mod cfg;
 pub mod channel;
 pub mod config;
 pub mod context;
@@ -30,9 +33,11 @@ pub mod lockfile;
 pub mod manifest;
 pub mod pkgid;
 pub mod protocol;
+pub mod resolver;
 pub mod source;
 pub mod swap;
 pub mod target;
 pub mod uuid;
 pub mod version;
 pub mod visibility;
+pub mod watch;
@@ -119,6 +119,11 @@ impl Protocol {
     /// This will attempt to download the url as a zip file and extract it to
     /// its queue directory.
     pub fn single_download(url: &str, dst: &PathBuf) -> Result<(), Fault> {
+        if crate::util::environment::is_frozen() == true {
+            return Err(Box::new(crate::error::Error::FrozenNetworkAccess(
+                url.to_string(),
+            )));
+        }
         let mut body_bytes = Vec::new();
         {
             let mut easy = Easy::new();
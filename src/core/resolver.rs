@@ -0,0 +1,428 @@
+//! Transitive dependency resolution over the ip catalog.
+//!
+//! Starting from a root ip's direct dependency constraints, [resolve]
+//! performs a depth-first walk of the dependency graph while maintaining a
+//! table of already-chosen versions. For each `(name, AnyVersion)`
+//! constraint encountered, the highest catalog version satisfying it is
+//! selected (reusing [version::get_target_version]). If a package is
+//! reached again under a constraint incompatible with the version already
+//! chosen for it, the resolver backtracks and retries with the next
+//! highest compatible version instead; if none exists, a
+//! [ResolveError::Conflict] is raised naming both constraints. A visited
+//! set carried along the current path detects and rejects dependency
+//! cycles.
+//!
+//! [resolve_minimal_versions] offers an alternate strategy in the style of
+//! Minimal Version Selection: instead of preferring the newest compatible
+//! release, every dependency's declared version is read as a *minimum*,
+//! and the selected version of a package is the maximum of every minimum
+//! requested against it. Cycles are tolerated rather than rejected (a
+//! requirement only ever grows, so revisiting a package already on the
+//! current path just folds in whatever new minimum it carries); a
+//! [ResolveError::Cycle] is only raised if that growth never settles.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::core::catalog::Catalog;
+use crate::core::ip::Ip;
+use crate::core::pkgid::PkgPart;
+use crate::core::version::{self, AnyVersion, Version};
+
+/// A single package resolved by [resolve]: the version chosen for it, and
+/// its own direct dependency constraints (carried along so a caller can
+/// pin them into a lockfile entry).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedIp {
+    name: PkgPart,
+    version: Version,
+    dependencies: Vec<(PkgPart, AnyVersion)>,
+}
+
+impl ResolvedIp {
+    pub fn get_name(&self) -> &PkgPart {
+        &self.name
+    }
+
+    pub fn get_version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn get_dependencies(&self) -> &Vec<(PkgPart, AnyVersion)> {
+        &self.dependencies
+    }
+}
+
+/// The complete transitive closure computed by [resolve], keyed by package
+/// name.
+#[derive(Debug, Default)]
+pub struct Resolution(HashMap<PkgPart, ResolvedIp>);
+
+impl Resolution {
+    pub fn get(&self, name: &PkgPart) -> Option<&ResolvedIp> {
+        self.0.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the resolved packages sorted by name, suitable for writing
+    /// out as a lockfile in a deterministic order.
+    pub fn into_entries(self) -> Vec<ResolvedIp> {
+        let mut entries: Vec<ResolvedIp> = self.0.into_values().collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResolveError {
+    /// No catalog entry exists for this package at all.
+    PackageNotFound(PkgPart),
+    /// `name` is constrained by two requirements that cannot both be
+    /// satisfied by a single chosen version.
+    Conflict {
+        name: PkgPart,
+        first: AnyVersion,
+        second: AnyVersion,
+    },
+    /// `path` revisits a package it already depends on through itself.
+    Cycle(Vec<PkgPart>),
+    /// No available version of `name` is as new as the highest minimum any
+    /// dependent requested (see [resolve_minimal_versions]).
+    Unsatisfiable { name: PkgPart, required: Version },
+}
+
+impl std::error::Error for ResolveError {}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PackageNotFound(name) => write!(
+                f,
+                "no known version of ip {:?} satisfies its dependents",
+                name.as_ref()
+            ),
+            Self::Conflict {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "version conflict for ip {:?}: cannot satisfy both {} and {}",
+                name.as_ref(),
+                first,
+                second
+            ),
+            Self::Cycle(path) => {
+                write!(f, "cyclic dependency detected: ")?;
+                for (i, name) in path.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " -> ")?;
+                    }
+                    write!(f, "{}", name.as_ref())?;
+                }
+                Ok(())
+            }
+            Self::Unsatisfiable { name, required } => write!(
+                f,
+                "no available version of ip {:?} is as new as the required minimum {}",
+                name.as_ref(),
+                required
+            ),
+        }
+    }
+}
+
+/// Checks if `version` fulfills `constraint`.
+fn satisfies(constraint: &AnyVersion, version: &Version) -> bool {
+    match constraint {
+        AnyVersion::Specific(pv) => version::is_compatible(pv, version),
+        AnyVersion::Latest => true,
+    }
+}
+
+/// Collects every ip known to `catalog` (installed, downloaded, or
+/// available through a channel) under the name `name`.
+fn candidates<'a>(catalog: &'a Catalog, name: &PkgPart) -> Vec<&'a Ip> {
+    let mut found = Vec::new();
+    if let Some(uuids) = catalog.mappings().get(name) {
+        for uuid in uuids {
+            if let Some(lvl) = catalog.inner().get(uuid) {
+                found.extend(lvl.get_installations().iter());
+                found.extend(lvl.get_downloads().iter());
+                found.extend(lvl.get_availability().iter());
+            }
+        }
+    }
+    found
+}
+
+/// Resolves the complete transitive dependency closure starting from
+/// `root`, choosing concrete versions out of `catalog` for every
+/// encountered constraint.
+pub fn resolve(root: &Ip, catalog: &Catalog) -> Result<Resolution, ResolveError> {
+    let mut chosen: HashMap<PkgPart, Version> = HashMap::new();
+    let mut first_constraint: HashMap<PkgPart, AnyVersion> = HashMap::new();
+    let mut resolved: HashMap<PkgPart, ResolvedIp> = HashMap::new();
+    let mut path: Vec<PkgPart> = Vec::new();
+
+    let root_name = root.get_man().get_ip().get_name().clone();
+    let root_version = root.get_man().get_ip().get_version().clone();
+    chosen.insert(root_name.clone(), root_version.clone());
+    first_constraint.insert(root_name, AnyVersion::from(&root_version));
+
+    visit(root, catalog, &mut chosen, &mut first_constraint, &mut resolved, &mut path)?;
+    Ok(Resolution(resolved))
+}
+
+fn visit<'a>(
+    ip: &'a Ip,
+    catalog: &'a Catalog,
+    chosen: &mut HashMap<PkgPart, Version>,
+    first_constraint: &mut HashMap<PkgPart, AnyVersion>,
+    resolved: &mut HashMap<PkgPart, ResolvedIp>,
+    path: &mut Vec<PkgPart>,
+) -> Result<(), ResolveError> {
+    let name = ip.get_man().get_ip().get_name().clone();
+
+    if path.contains(&name) {
+        let mut cycle = path.clone();
+        cycle.push(name);
+        return Err(ResolveError::Cycle(cycle));
+    }
+    // its dependencies were already walked when it was first resolved
+    if resolved.contains_key(&name) {
+        return Ok(());
+    }
+
+    path.push(name.clone());
+
+    let deps: Vec<(PkgPart, AnyVersion)> = ip
+        .get_man()
+        .get_deps_list(false, true)
+        .into_iter()
+        .map(|(n, d)| (n.clone(), AnyVersion::Specific(d.get_version().clone())))
+        .collect();
+
+    resolved.insert(
+        name.clone(),
+        ResolvedIp {
+            name: name.clone(),
+            version: ip.get_man().get_ip().get_version().clone(),
+            dependencies: deps.clone(),
+        },
+    );
+
+    for (dep_name, constraint) in deps {
+        let options = candidates(catalog, &dep_name);
+        if options.is_empty() {
+            path.pop();
+            return Err(ResolveError::PackageNotFound(dep_name));
+        }
+
+        let dep_ip = match chosen.get(&dep_name).cloned() {
+            // a version was already chosen for this package on another branch
+            Some(existing) => {
+                if satisfies(&constraint, &existing) {
+                    options
+                        .iter()
+                        .find(|ip| ip.get_man().get_ip().get_version() == &existing)
+                        .copied()
+                } else {
+                    // backtrack: search for a different version compatible
+                    // with both the new constraint and this dependent
+                    let mut ranked = options.clone();
+                    ranked.sort_by(|a, b| {
+                        b.get_man()
+                            .get_ip()
+                            .get_version()
+                            .cmp(a.get_man().get_ip().get_version())
+                    });
+                    let first = first_constraint.get(&dep_name).cloned();
+                    match ranked.into_iter().find(|ip| {
+                        let v = ip.get_man().get_ip().get_version();
+                        satisfies(&constraint, v)
+                            && first.as_ref().map_or(true, |f| satisfies(f, v))
+                    }) {
+                        Some(ip) => {
+                            chosen.insert(dep_name.clone(), ip.get_man().get_ip().get_version().clone());
+                            // drop the entry recorded for the earlier version so
+                            // the newly chosen one is walked and recorded instead
+                            resolved.remove(&dep_name);
+                            Some(ip)
+                        }
+                        None => {
+                            let first = first.unwrap_or_else(|| AnyVersion::from(&existing));
+                            path.pop();
+                            return Err(ResolveError::Conflict {
+                                name: dep_name,
+                                first,
+                                second: constraint,
+                            });
+                        }
+                    }
+                }
+            }
+            None => {
+                let versions: Vec<&Version> = options
+                    .iter()
+                    .map(|ip| ip.get_man().get_ip().get_version())
+                    .collect();
+                match version::get_target_version(&constraint, &versions) {
+                    Ok(v) => {
+                        let found = options
+                            .iter()
+                            .find(|ip| ip.get_man().get_ip().get_version() == &v)
+                            .copied();
+                        chosen.insert(dep_name.clone(), v);
+                        first_constraint.insert(dep_name.clone(), constraint);
+                        found
+                    }
+                    Err(_) => {
+                        path.pop();
+                        return Err(ResolveError::PackageNotFound(dep_name));
+                    }
+                }
+            }
+        };
+
+        if let Some(dep_ip) = dep_ip {
+            visit(dep_ip, catalog, chosen, first_constraint, resolved, path)?;
+        }
+    }
+
+    path.pop();
+    Ok(())
+}
+
+/// Resolves the complete transitive dependency closure starting from
+/// `root`, using Minimal Version Selection instead of [resolve]'s
+/// newest-compatible strategy: every dependency's declared version is
+/// read as a minimum (via [version::PartialVersion::as_floor_version]),
+/// and a package's selected version is always the maximum minimum
+/// requested of it by any module that depends on it, picked from the
+/// lowest catalog release meeting that floor. Because a requirement only
+/// ever grows, a package is re-walked — folding its own requirements back
+/// in — each time a higher minimum is discovered for it, until the whole
+/// graph reaches a fixed point.
+pub fn resolve_minimal_versions(root: &Ip, catalog: &Catalog) -> Result<Resolution, ResolveError> {
+    let root_name = root.get_man().get_ip().get_name().clone();
+    let root_version = root.get_man().get_ip().get_version().clone();
+
+    // the highest minimum any visited module has requested of each package
+    let mut requirement: HashMap<PkgPart, Version> = HashMap::new();
+    let mut resolved: HashMap<PkgPart, ResolvedIp> = HashMap::new();
+    // who first requested a package, for reporting the chain if MVS never settles
+    let mut requested_by: HashMap<PkgPart, PkgPart> = HashMap::new();
+
+    requirement.insert(root_name.clone(), root_version);
+
+    let mut queue: VecDeque<PkgPart> = VecDeque::new();
+    queue.push_back(root_name.clone());
+
+    // requirements only increase, and there's only ever one catalog's
+    // worth of distinct (name, version) pairs to settle on, so this many
+    // re-visits is generous headroom before concluding the graph diverges
+    let divergence_limit = (catalog.inner().len() + 1) * 64;
+    let mut steps = 0usize;
+
+    while let Some(name) = queue.pop_front() {
+        steps += 1;
+        if steps > divergence_limit {
+            let mut chain = vec![name.clone()];
+            let mut cursor = &name;
+            while let Some(parent) = requested_by.get(cursor) {
+                if chain.contains(parent) {
+                    break;
+                }
+                chain.push(parent.clone());
+                cursor = parent;
+            }
+            chain.reverse();
+            return Err(ResolveError::Cycle(chain));
+        }
+
+        let required = requirement.get(&name).unwrap().clone();
+
+        // a previously resolved entry still satisfies a requirement no
+        // higher than what it was resolved under
+        if let Some(existing) = resolved.get(&name) {
+            if existing.get_version() >= &required {
+                continue;
+            }
+        }
+
+        let ip: &Ip = if name == root_name {
+            root
+        } else {
+            let options = candidates(catalog, &name);
+            if options.is_empty() {
+                return Err(ResolveError::PackageNotFound(name));
+            }
+            match options
+                .iter()
+                .filter(|ip| ip.get_man().get_ip().get_version() >= &required)
+                .min_by(|a, b| {
+                    a.get_man()
+                        .get_ip()
+                        .get_version()
+                        .cmp(b.get_man().get_ip().get_version())
+                })
+                .copied()
+            {
+                Some(ip) => ip,
+                None => {
+                    return Err(ResolveError::Unsatisfiable {
+                        name,
+                        required,
+                    })
+                }
+            }
+        };
+
+        let deps: Vec<(PkgPart, AnyVersion)> = ip
+            .get_man()
+            .get_deps_list(false, true)
+            .into_iter()
+            .map(|(n, d)| (n.clone(), AnyVersion::Specific(d.get_version().clone())))
+            .collect();
+
+        resolved.insert(
+            name.clone(),
+            ResolvedIp {
+                name: name.clone(),
+                version: ip.get_man().get_ip().get_version().clone(),
+                dependencies: deps.clone(),
+            },
+        );
+
+        for (dep_name, constraint) in deps {
+            let floor = match &constraint {
+                AnyVersion::Specific(pv) => pv.as_floor_version(),
+                AnyVersion::Latest => Version::new(),
+            };
+
+            let grew = match requirement.get(&dep_name) {
+                Some(current) if current >= &floor => false,
+                _ => {
+                    requirement.insert(dep_name.clone(), floor);
+                    true
+                }
+            };
+
+            if dep_name == name {
+                // self-dependency: already folded into this module's own requirement
+                continue;
+            }
+
+            if grew || !resolved.contains_key(&dep_name) {
+                requested_by.entry(dep_name.clone()).or_insert_with(|| name.clone());
+                queue.push_back(dep_name);
+            }
+        }
+    }
+
+    Ok(Resolution(resolved))
+}
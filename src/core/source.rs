@@ -79,6 +79,70 @@ impl Source {
     }
 }
 
+pub type Mirrors = Vec<Mirror>;
+
+/// A [Mirror] transparently redirects fetches for a matching original source
+/// to a replacement [Source], analogous to cargo's `[source.<name>]
+/// replace-with = ...`.
+///
+/// Matching is performed against the original source's url or protocol (see
+/// [Mirror::matches]). The original `tag` is preserved across the swap so
+/// protocols that rely on it (checkout refs, archive entry names, ...)
+/// continue to resolve correctly against the mirror.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Mirror {
+    /// The original url or protocol prefix to match against a [Source].
+    #[serde(rename = "match")]
+    pattern: String,
+    replace: String,
+}
+
+impl Mirror {
+    pub fn new() -> Self {
+        Self {
+            pattern: String::new(),
+            replace: String::new(),
+        }
+    }
+
+    pub fn get_pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Checks if `src` should be replaced by this mirror, matching against
+    /// the source's protocol (if set) or its url.
+    pub fn matches(&self, src: &Source) -> bool {
+        match src.get_protocol() {
+            Some(p) => p == &self.pattern,
+            None => src.get_url().starts_with(&self.pattern),
+        }
+    }
+
+    /// Applies this mirror's replacement url to `src`, preserving the
+    /// original `tag` so ref/checksum-sensitive protocols still work.
+    fn apply(&self, src: Source) -> Source {
+        Source::new()
+            .url(self.replace.clone())
+            .protocol(src.get_protocol().cloned())
+            .tag(src.get_tag().cloned())
+    }
+}
+
+/// Searches `mirrors` for the first entry matching `src` and, if found,
+/// returns `src` rewritten to point at the mirror's replacement url.
+///
+/// This is applied before [Source::replace_vars_in_url] so mirrors may
+/// themselves contain variables to substitute. Any expected checksum tied to
+/// the original source is left untouched, so a mirror cannot silently serve
+/// different bytes without also failing that verification.
+pub fn apply_mirrors(src: Source, mirrors: &[Mirror]) -> Source {
+    match mirrors.iter().find(|m| m.matches(&src)) {
+        Some(m) => m.apply(src),
+        None => src,
+    }
+}
+
 impl From<Option<Source>> for Source {
     fn from(value: Option<Source>) -> Self {
         match value {
@@ -48,7 +48,14 @@ impl<'a> Store<'a> {
             std::fs::remove_dir_all(&store_ip_dir)?;
         }
         // copy the repository to the store location
-        filesystem::copy(&ip.get_root(), &store_ip_dir, false)?;
+        filesystem::copy(
+            &ip.get_root(),
+            &store_ip_dir,
+            None,
+            None,
+            filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        )?;
         Ok(store_ip_dir)
     }
 
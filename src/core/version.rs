@@ -258,6 +258,23 @@ impl PartialVersion {
                 .label(self.label.clone()),
         )
     }
+
+    /// Fills in any missing minor/micro positions with their lowest possible
+    /// value, for use as the inclusive lower bound of a [VersionRange].
+    pub fn as_floor_version(&self) -> Version {
+        self.clone().into()
+    }
+
+    /// Fills in any missing minor/micro positions with their highest possible
+    /// value, for use as the inclusive upper bound of a [VersionRange].
+    pub fn as_ceiling_version(&self) -> Version {
+        Version {
+            major: self.major,
+            minor: self.minor.unwrap_or(VerNum::MAX),
+            micro: self.micro.unwrap_or(VerNum::MAX),
+            label: self.label.clone(),
+        }
+    }
 }
 
 impl Display for PartialVersion {
@@ -381,6 +398,54 @@ impl Serialize for PartialVersion {
     }
 }
 
+/// An inclusive `low:high` pair of partial semver expressions.
+///
+/// Either bound may be omitted (e.g. `:1.4`, `1.2:`) to leave that side
+/// unbounded.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VersionRange {
+    lower: Option<PartialVersion>,
+    upper: Option<PartialVersion>,
+}
+
+impl VersionRange {
+    /// Checks if `ver` satisfies `low <= ver <= high`, treating a missing
+    /// bound as unbounded.
+    pub fn in_range(&self, ver: &Version) -> bool {
+        if let Some(low) = &self.lower {
+            if ver < &low.as_floor_version() {
+                return false;
+            }
+        }
+        if let Some(high) = &self.upper {
+            if ver > &high.as_ceiling_version() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (low, high) = match s.split_once(':') {
+            Some(parts) => parts,
+            None => return Err(VersionError::MissingRangeDelim),
+        };
+        let lower = match low.trim().is_empty() {
+            true => None,
+            false => Some(PartialVersion::from_str(low)?),
+        };
+        let upper = match high.trim().is_empty() {
+            true => None,
+            false => Some(PartialVersion::from_str(high)?),
+        };
+        Ok(Self { lower, upper })
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Ord, Eq, Hash)]
 pub struct Version {
     major: VerNum,
@@ -612,6 +677,7 @@ pub enum VersionError {
     InvalidDigit(ParseIntError),
     EmptyLabel,
     InvalidChar(char),
+    MissingRangeDelim,
 }
 
 impl std::error::Error for VersionError {}
@@ -632,6 +698,7 @@ impl Display for VersionError {
             InvalidChar(c) => write!(f, "invalid character '{}' in version label", c),
             ExtraLevels(l) => write!(f, "too many version positions; found {} expected 3", l),
             InvalidDigit(_) => write!(f, "invalid digit in version"),
+            MissingRangeDelim => write!(f, "missing ':' delimiter between low and high version"),
         }
     }
 }
@@ -1023,4 +1090,49 @@ mod test {
         assert_eq!(v0.in_domain(&v1), true);
         assert_eq!(v1.in_domain(&v0), true);
     }
+
+    mod version_range {
+        use super::*;
+
+        #[test]
+        fn parses_bounds() {
+            let vr = VersionRange::from_str("1.2:1.4").unwrap();
+            assert_eq!(vr.lower, Some(PartialVersion::new().major(1).minor(2)));
+            assert_eq!(vr.upper, Some(PartialVersion::new().major(1).minor(4)));
+
+            let vr = VersionRange::from_str(":1.4").unwrap();
+            assert_eq!(vr.lower, None);
+            assert_eq!(vr.upper, Some(PartialVersion::new().major(1).minor(4)));
+
+            let vr = VersionRange::from_str("1.2:").unwrap();
+            assert_eq!(vr.lower, Some(PartialVersion::new().major(1).minor(2)));
+            assert_eq!(vr.upper, None);
+
+            assert_eq!(
+                VersionRange::from_str("1.2.0"),
+                Err(VersionError::MissingRangeDelim)
+            );
+        }
+
+        #[test]
+        fn checks_in_range() {
+            let vr = VersionRange::from_str("1.2:1.4").unwrap();
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(1).micro(9)), false);
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(2).micro(0)), true);
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(3).micro(7)), true);
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(4).micro(99)), true);
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(5).micro(0)), false);
+            assert_eq!(vr.in_range(&Version::new().major(2).minor(0).micro(0)), false);
+
+            // unbounded sides
+            let vr = VersionRange::from_str(":1.4").unwrap();
+            assert_eq!(vr.in_range(&Version::new().major(0).minor(0).micro(1)), true);
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(4).micro(0)), true);
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(5).micro(0)), false);
+
+            let vr = VersionRange::from_str("1.2:").unwrap();
+            assert_eq!(vr.in_range(&Version::new().major(1).minor(1).micro(9)), false);
+            assert_eq!(vr.in_range(&Version::new().major(99).minor(0).micro(0)), true);
+        }
+    }
 }
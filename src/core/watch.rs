@@ -0,0 +1,116 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// How often the filesystem is polled for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a quiet period must last (no new changes observed) before a
+/// burst of edits is treated as settled and a rebuild is triggered. Guards
+/// against re-planning mid-save, since some editors write a file several
+/// times in quick succession.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// A stat-based snapshot of a file: cheap to take and enough to notice an
+/// edit, a truncation, or a touch-but-unmodified no-op.
+type Stamp = (u64, u64);
+
+/// A polling filesystem watcher over a fixed set of paths.
+///
+/// No new dependency is pulled in for something this small and infrequent
+/// (a human editing source files, not a high-frequency event stream); the
+/// mtime+size stamp is the same cheap check `core::cache::ParseCache` uses
+/// for its fast path.
+pub struct Watcher {
+    snapshots: HashMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    /// Takes an initial snapshot of `files` so the first [Self::wait_for_changes]
+    /// call only reports edits made after this point.
+    pub fn new(files: &Vec<PathBuf>) -> Self {
+        let mut snapshots = HashMap::new();
+        for f in files {
+            snapshots.insert(f.clone(), Self::stamp(f));
+        }
+        Self { snapshots }
+    }
+
+    fn stamp(path: &PathBuf) -> Stamp {
+        match fs::metadata(path) {
+            Ok(meta) => {
+                let modified = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                (modified, meta.len())
+            }
+            // a missing/unreadable file is its own distinct stamp so its
+            // reappearance (or a permissions fix) is still noticed
+            Err(_) => (0, 0),
+        }
+    }
+
+    /// Blocks until at least one of `files` changes, then waits out
+    /// [DEBOUNCE] to let a burst of edits settle before returning every
+    /// path that changed along the way.
+    pub fn wait_for_changes(&mut self, files: &Vec<PathBuf>) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let changed = self.poll_once(files);
+            if changed.is_empty() == false {
+                return self.settle(files, changed);
+            }
+        }
+    }
+
+    fn poll_once(&mut self, files: &Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for f in files {
+            let stamp = Self::stamp(f);
+            match self.snapshots.get(f) {
+                Some(prior) if *prior == stamp => (),
+                _ => {
+                    changed.push(f.clone());
+                    self.snapshots.insert(f.clone(), stamp);
+                }
+            }
+        }
+        changed
+    }
+
+    fn settle(&mut self, files: &Vec<PathBuf>, first: Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut changed: HashSet<PathBuf> = first.into_iter().collect();
+        let mut last_change = Instant::now();
+        while last_change.elapsed() < DEBOUNCE {
+            thread::sleep(POLL_INTERVAL);
+            let more = self.poll_once(files);
+            if more.is_empty() == false {
+                changed.extend(more);
+                last_change = Instant::now();
+            }
+        }
+        changed.into_iter().collect()
+    }
+}
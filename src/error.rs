@@ -67,6 +67,16 @@ pub enum Error {
     ProtocolProcFailed(LastError),
     #[error("no protocol named {0:?}")]
     ProtocolNotFound(String),
+    #[error("network access to {0:?} is forbidden while --frozen is set")]
+    FrozenNetworkAccess(String),
+    #[error("ip {0} is not available offline and --offline was specified{1}")]
+    OfflineDependencyMissing(IpSpec, Hint),
+    #[error("the lockfile is out of date, but --locked was specified{0}")]
+    LockedOutOfDate(Hint),
+    #[error("failed to acquire a lock on {0:?}: {1}")]
+    CacheLockFailed(PathBuf, LastError),
+    #[error("another orbit process is using {0:?} and --locked was specified")]
+    CacheLockContended(PathBuf),
     #[error("failed to modify configuration: {0}")]
     ConfigNotSaved(LastError),
     #[error("configuration field {0:?} does not store a list")]
@@ -81,6 +91,8 @@ pub enum Error {
     SourceCodeInvalidSyntax(PathBuf, LastError),
     #[error("failed to process ip graph: {0}")]
     IpGraphFailed(LastError),
+    #[error("failed to resolve dependencies: {0}")]
+    DependencyResolutionFailed(LastError),
     #[error("failed to parse identifier: {0}")]
     CrossIdentifierParsingFailed(LastError),
     #[error("duplicate identifier \"{0}\" found in the following source files:\n\n  location 1: {1}{2}\n  location 2: {3}{4}{5}")]
@@ -161,6 +173,8 @@ pub enum Error {
     ConfigIncludeInNonglobal,
     #[error("expects 22 characters but found {0}")]
     IdNot22Chars(usize),
+    #[error("cyclic dependency detected between unit \"{0}\" and unit \"{1}\"")]
+    CompileOrderCycle(LangIdentifier, LangIdentifier),
 }
 
 #[derive(Debug, PartialEq)]
@@ -216,6 +230,7 @@ pub enum Hint {
     RegenerateLockfile,
     ShowVersions,
     ShowConfigFiles,
+    DisableOffline,
 }
 
 impl Display for Hint {
@@ -256,6 +271,7 @@ impl Display for Hint {
             Self::PublishWithReady => "use the \"--ready\" flag to publish the ip to its channels",
             Self::RegenerateLockfile => "verify the ip's lockfile exists and is up to date",
             Self::ShowVersions => "use `orbit view <ip> --versions` to see all known versions",
+            Self::DisableOffline => "re-run without \"--offline\" to fetch it over the network",
             Self::ShowConfigFiles => {
                 "use `orbit config --list` to see the list of current configuration files"
             }
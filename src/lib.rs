@@ -5,11 +5,133 @@ mod core;
 pub mod util;
 
 use crate::commands::orbit::*;
+use crate::core::alias;
+use crate::core::alias::Aliases;
+use crate::core::config;
+use crate::core::context::Context;
+use crate::util::environment;
+use crate::util::seqalin;
 use clif::cmd::Command;
 use clif::cmd::FromCli;
 use clif::*;
 use colored::*;
 
+enum AliasOutcome {
+    Resolved(Vec<String>),
+    /// The leading token matched neither a built-in subcommand nor a
+    /// configured alias, but came within [SUGGEST_THRESHOLD] edits of one.
+    Suggest { given: String, suggestion: String },
+}
+
+/// Maximum edit distance (see [seqalin::sel_min_edit_str]) for an unknown
+/// subcommand to be offered as a "did you mean" suggestion.
+const SUGGEST_THRESHOLD: usize = 4;
+
+/// Short-flag letters that take a value somewhere in the CLI (e.g. `-t` for
+/// `--target` in [commands::build::Build]/[commands::test::Test]), and so
+/// may appear with their value attached (`-ttop_entity`) rather than as a
+/// separate token. This runs ahead of subcommand dispatch, so it cannot
+/// know which subcommand's schema is actually in play; treating the letter
+/// as reserved in every subcommand is the conservative choice, since
+/// wrongly bundle-expanding a value-taking switch corrupts the invocation,
+/// while wrongly leaving a same-lettered plain switch unexpanded just means
+/// the user spells it out (`-s -t` instead of `-st`).
+const VALUE_TAKING_SWITCHES: &[char] = &['t', 'v', 'm'];
+
+/// Expands POSIX-style bundled short flags (e.g. `-hv` into `-h -v`) before
+/// `args` ever reaches [Cli], and honors a literal `--` as the end of flag
+/// processing by passing every token after it through unchanged.
+///
+/// A token consisting of a single leading `-` followed by two or more
+/// alphabetic characters is treated as a bundle; a lone switch like `-t`, a
+/// `--long` option, and any token after `--` are left untouched. A token is
+/// also left untouched, rather than bundle-expanded, when its first letter
+/// is one of [VALUE_TAKING_SWITCHES], since `-ttop_entity` is `-t` with its
+/// value attached, not a bundle of single-char switches `t`, `o`, `p`, ...
+pub fn expand_short_flags(args: Vec<String>) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(args.len());
+    let mut past_terminator = false;
+    for arg in args {
+        if past_terminator {
+            expanded.push(arg);
+            continue;
+        }
+        if arg == "--" {
+            past_terminator = true;
+            expanded.push(arg);
+            continue;
+        }
+        let is_bundle = arg.starts_with('-')
+            && arg.starts_with("--") == false
+            && arg.len() > 2
+            && arg.chars().skip(1).all(|c| c.is_ascii_alphabetic())
+            && !VALUE_TAKING_SWITCHES.contains(&arg.chars().nth(1).unwrap());
+        if is_bundle {
+            expanded.extend(arg.chars().skip(1).map(|c| format!("-{}", c)));
+        } else {
+            expanded.push(arg);
+        }
+    }
+    expanded
+}
+
+/// Resolves a user-defined alias (see [core::alias]) found in the leading
+/// subcommand position of `args` against the loaded configuration,
+/// splicing its expansion back into the argument stream before dispatch.
+///
+/// A built-in subcommand ([commands::orbit::SUBCOMMANDS]) always shadows an
+/// alias of the same name. Falls back to returning `args` unchanged if the
+/// configuration cannot be loaded (e.g. `$ORBIT_HOME` is not yet resolvable).
+///
+/// If the leading token matches neither a built-in subcommand nor a
+/// configured alias, but is a close misspelling of one, prints a "did you
+/// mean" suggestion and exits rather than letting dispatch fail with a
+/// generic unknown-argument error.
+pub fn resolve_aliases(args: Vec<String>) -> Vec<String> {
+    match try_resolve_aliases(&args) {
+        Ok(AliasOutcome::Resolved(resolved)) => resolved,
+        Ok(AliasOutcome::Suggest { given, suggestion }) => {
+            eprintln!(
+                "{}: unknown argument '{}'\n\nDid you mean '{}'?",
+                "error".red().bold(),
+                given.yellow(),
+                suggestion.green()
+            );
+            std::process::exit(101);
+        }
+        Err(_) => args,
+    }
+}
+
+fn try_resolve_aliases(args: &[String]) -> Result<AliasOutcome, Box<dyn std::error::Error>> {
+    let context = Context::new()
+        .home(environment::ORBIT_HOME)?
+        .current_ip_dir(environment::ORBIT_IP_PATH)?
+        .settings(config::CONFIG_FILE)?;
+
+    let mut args = args.to_vec();
+    let empty = Aliases::new();
+    let aliases = context.get_config().get_aliases().unwrap_or(&empty);
+
+    if let Some(index) = alias::find_subcommand_index(&args, &["--color"]) {
+        let token = args[index].clone();
+        let is_known = commands::orbit::SUBCOMMANDS.contains(&token.as_str());
+        let is_alias = aliases.contains_key(&token);
+        if is_known == false && is_alias == false {
+            let mut bank: Vec<&str> = commands::orbit::SUBCOMMANDS.to_vec();
+            bank.extend(aliases.keys().map(|k| k.as_str()));
+            if let Some(suggestion) = seqalin::sel_min_edit_str(&token, &bank, SUGGEST_THRESHOLD) {
+                return Ok(AliasOutcome::Suggest {
+                    given: token,
+                    suggestion: suggestion.to_string(),
+                });
+            }
+        }
+        alias::resolve(&mut args, index, aliases, commands::orbit::SUBCOMMANDS);
+    }
+    Ok(AliasOutcome::Resolved(args))
+}
+
 pub fn go() -> u8 {
     // interface level
     let mut cli = Cli::new()
@@ -41,3 +163,42 @@ pub fn go() -> u8 {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn v(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn bundled_short_flags_are_split() {
+        let result = expand_short_flags(v(&["orbit", "build", "-hv"]));
+        assert_eq!(result, v(&["orbit", "build", "-h", "-v"]));
+    }
+
+    #[test]
+    fn lone_switch_is_untouched() {
+        let result = expand_short_flags(v(&["orbit", "get", "-s", "top"]));
+        assert_eq!(result, v(&["orbit", "get", "-s", "top"]));
+    }
+
+    #[test]
+    fn long_option_is_untouched() {
+        let result = expand_short_flags(v(&["orbit", "--color", "always"]));
+        assert_eq!(result, v(&["orbit", "--color", "always"]));
+    }
+
+    #[test]
+    fn tokens_after_terminator_are_untouched() {
+        let result = expand_short_flags(v(&["orbit", "new", "--", "-hv"]));
+        assert_eq!(result, v(&["orbit", "new", "--", "-hv"]));
+    }
+
+    #[test]
+    fn attached_value_on_a_value_taking_switch_is_untouched() {
+        let result = expand_short_flags(v(&["orbit", "build", "-ttop_entity"]));
+        assert_eq!(result, v(&["orbit", "build", "-ttop_entity"]));
+    }
+}
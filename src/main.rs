@@ -3,5 +3,7 @@ use orbit::Orbit;
 use std::env;
 
 fn main() -> ExitCode {
-    Cli::default().parse(env::args()).go::<Orbit>()
+    let args = orbit::expand_short_flags(env::args().collect());
+    let args = orbit::resolve_aliases(args);
+    Cli::default().parse(args.into_iter()).go::<Orbit>()
 }
@@ -0,0 +1,136 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Streams a sorted fileset into a single, solid-compressed tarball.
+//!
+//! Modeled after the rust-installer approach: an HDL source tree tends to
+//! carry many near-identical files (testbenches, generated wrappers), so a
+//! wide LZMA2 dictionary lets the encoder deduplicate across the whole
+//! archive instead of per-file, shrinking the result far more than the
+//! typical 8 MiB window would.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use tar::{Archive, Builder};
+use xz2::read::XzDecoder;
+use xz2::stream::{Check, Filters, LzmaOptions, MtStreamBuilder};
+use xz2::write::XzEncoder;
+
+use super::anyerror::{AnyError, Fault};
+use super::checksum;
+use super::filesystem;
+use crate::core::ip::Ip;
+use crate::core::manifest::ORBIT_SUM_FILE;
+
+/// Default LZMA2 dictionary/window size, in bytes: 64 MiB rather than xz's
+/// usual 8 MiB, so a solid archive can see across an entire HDL source tree.
+pub const DEFAULT_DICT_SIZE: u32 = 64 * 1024 * 1024;
+
+/// Default LZMA preset (0-9, higher is slower but smaller).
+pub const DEFAULT_LEVEL: u32 = 6;
+
+/// Knobs for [pack]: compression `level`, dictionary `window` size, and an
+/// optional worker-thread count for multithreaded encoding.
+pub struct PackOptions {
+    pub level: u32,
+    pub window: u32,
+    pub threads: Option<u32>,
+}
+
+impl Default for PackOptions {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_LEVEL,
+            window: DEFAULT_DICT_SIZE,
+            threads: None,
+        }
+    }
+}
+
+/// Streams `files` (relative to `root`) into a single xz-compressed tarball
+/// written to `dest`.
+///
+/// Entries are appended in the order given by `files`, so passing it the
+/// already-sorted list from [filesystem::gather_current_files] produces a
+/// byte-reproducible archive.
+pub fn pack(
+    root: &PathBuf,
+    files: &[String],
+    dest: &PathBuf,
+    options: &PackOptions,
+) -> Result<(), Fault> {
+    let mut lzma_opts = LzmaOptions::new_preset(options.level)?;
+    lzma_opts.dict_size(options.window);
+
+    let mut filters = Filters::new();
+    filters.lzma2(&lzma_opts);
+
+    let mut mt = MtStreamBuilder::new();
+    mt.filters(filters).check(Check::Crc64);
+    if let Some(threads) = options.threads {
+        mt.threads(threads);
+    }
+    let stream = mt.encoder()?;
+
+    let writer = File::create(dest)?;
+    let xz_writer = XzEncoder::new_stream(writer, stream);
+    let mut tar_builder = Builder::new(xz_writer);
+
+    for file in files {
+        tar_builder.append_path_with_name(root.join(file), file)?;
+    }
+
+    let xz_writer = tar_builder.into_inner()?;
+    xz_writer.finish()?;
+
+    Ok(())
+}
+
+/// Reverses [pack]: decompresses and unpacks the tarball at `archive` into
+/// `dest`, then verifies the extracted tree still matches its embedded
+/// [ORBIT_SUM_FILE] checksum, if one is present.
+///
+/// Assumes `dest` does not already exist.
+pub fn unpack(archive: &PathBuf, dest: &PathBuf) -> Result<(), Fault> {
+    std::fs::create_dir_all(dest)?;
+
+    let reader = BufReader::new(File::open(archive)?);
+    let xz_reader = XzDecoder::new(reader);
+    let mut tar_archive = Archive::new(xz_reader);
+    tar_archive.unpack(dest)?;
+
+    // validate the embedded checksum, if the packed tree carried one
+    if let Some(expected) = Ip::read_cache_checksum(dest) {
+        let extracted_files = filesystem::gather_current_files(
+            dest,
+            true,
+            filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
+        );
+        let actual = checksum::checksum(&extracted_files, dest);
+        if actual != expected {
+            return Err(AnyError(format!(
+                "archive {:?} failed its {} checksum verification after extraction",
+                archive, ORBIT_SUM_FILE
+            )))?;
+        }
+    }
+
+    Ok(())
+}
@@ -126,6 +126,8 @@ mod test {
         let test_files = crate::util::filesystem::gather_current_files(
             &std::path::PathBuf::from("./tests/t3"),
             false,
+            crate::util::filesystem::default_thread_count(),
+            &std::collections::HashMap::new(),
         );
         println!("{:?}", test_files);
         let checksum = crate::util::checksum::checksum(
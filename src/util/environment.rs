@@ -295,3 +295,48 @@ pub const ORBIT_OUT_DIR: &str = "ORBIT_OUT_DIR";
 pub const ORBIT_CHAN_INDEX: &str = "ORBIT_CHAN_INDEX";
 
 pub const ORBIT_ENV_PREFIX: &str = "ORBIT_ENV_";
+
+/// Set (to any value) to force catalog/unit scanning onto a single thread,
+/// for debugging parallel scans that behave differently under `rayon`.
+pub const ORBIT_SINGLE_THREADED: &str = "ORBIT_SINGLE_THREADED";
+
+/// Checks if [ORBIT_SINGLE_THREADED] is set, as an escape hatch from the
+/// `rayon`-parallelized catalog and unit scans.
+pub fn is_single_threaded() -> bool {
+    std::env::var(ORBIT_SINGLE_THREADED).is_ok()
+}
+
+/// Set (mirroring cargo's `--locked`) when the resolved dependency set must
+/// not cause `Orbit.lock` to change from what is already on disk.
+pub const ORBIT_LOCKED: &str = "ORBIT_LOCKED";
+
+/// Set (mirroring cargo's `--frozen`) when, in addition to [ORBIT_LOCKED],
+/// no network or catalog access is permitted.
+pub const ORBIT_FROZEN: &str = "ORBIT_FROZEN";
+
+/// Checks if [ORBIT_LOCKED] is set.
+pub fn is_locked() -> bool {
+    std::env::var(ORBIT_LOCKED).is_ok()
+}
+
+/// Checks if [ORBIT_FROZEN] is set.
+pub fn is_frozen() -> bool {
+    std::env::var(ORBIT_FROZEN).is_ok()
+}
+
+/// Set by `--yes`/`-y` to auto-accept every confirmation prompt.
+pub const ORBIT_ASSUME_YES: &str = "ORBIT_ASSUME_YES";
+
+/// Set by `--non-interactive` to never read from stdin for a confirmation
+/// prompt, instead immediately resolving to the prompt's stated default.
+pub const ORBIT_NON_INTERACTIVE: &str = "ORBIT_NON_INTERACTIVE";
+
+/// Checks if [ORBIT_ASSUME_YES] is set.
+pub fn is_assume_yes() -> bool {
+    std::env::var(ORBIT_ASSUME_YES).is_ok()
+}
+
+/// Checks if [ORBIT_NON_INTERACTIVE] is set.
+pub fn is_non_interactive() -> bool {
+    std::env::var(ORBIT_NON_INTERACTIVE).is_ok()
+}
@@ -1,17 +1,128 @@
-use crate::core::fileset;
 use crate::core::lockfile;
 use crate::core::manifest;
+use crate::util::environment;
+use crate::util::filetype;
 use fs_extra;
 use home::home_dir;
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::env;
 use std::env::current_dir;
 use std::ffi::OsStr;
 use std::path::PathBuf;
 use std::path::{Component, Path};
+use std::sync::Mutex;
 
 use super::anyerror::Fault;
 
+/// Abstracts over the path-like byte containers this module normalizes,
+/// modeled on the long-removed `std::path::BytesContainer`.
+///
+/// Letting separator rewriting and component folding work against raw
+/// bytes (rather than requiring a `.to_str().unwrap()` up front) means a
+/// path with a non-UTF-8 component no longer panics partway through
+/// normalization; only the final display boundary (see [into_std_str])
+/// lossily stringifies what can't be represented as UTF-8.
+pub trait BytesContainer {
+    fn container_as_bytes(&self) -> Cow<[u8]>;
+}
+
+impl BytesContainer for str {
+    fn container_as_bytes(&self) -> Cow<[u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl BytesContainer for [u8] {
+    fn container_as_bytes(&self) -> Cow<[u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl BytesContainer for OsStr {
+    #[cfg(unix)]
+    fn container_as_bytes(&self) -> Cow<[u8]> {
+        Cow::Borrowed(std::os::unix::ffi::OsStrExt::as_bytes(self))
+    }
+
+    #[cfg(not(unix))]
+    fn container_as_bytes(&self) -> Cow<[u8]> {
+        // non-unix `OsStr`s are not guaranteed to be representable as raw
+        // bytes; fall back to a lossy re-encoding so callers still get
+        // something to normalize
+        Cow::Owned(self.to_string_lossy().into_owned().into_bytes())
+    }
+}
+
+/// Builds the full (non-filtering) [filetype] registry for a walk, falling
+/// back to the built-in defaults if `file_types` contains an invalid pattern.
+fn resolve_types(file_types: &HashMap<String, Vec<String>>) -> ignore::types::Types {
+    filetype::build_types(file_types)
+        .unwrap_or_else(|_| filetype::build_types(&HashMap::new()).expect("default file types are valid"))
+}
+
+/// Name of the file that lists allowlist patterns to force-include paths an
+/// `.orbitignore` would otherwise drop.
+pub const ORBIT_INCLUDE_FILE: &str = ".orbitinclude";
+
+/// Builds an allowlist matcher so files an `.orbitignore` would otherwise
+/// exclude can be force-included.
+///
+/// Reads glob patterns (one per line, blank lines and `#` comments skipped,
+/// `!` negation supported just like `.gitignore`) from an `.orbitinclude`
+/// file at the root of `path`, if one exists. Each path in `keep` is also
+/// force-included, anchored to its path relative to `path`.
+///
+/// Falls back to an empty (no-op) matcher on any pattern error so a bad
+/// `.orbitinclude` entry degrades to "nothing force-included" rather than
+/// aborting the walk.
+fn resolve_overrides(path: &PathBuf, keep: Option<&HashSet<PathBuf>>) -> Override {
+    build_overrides(path, keep).unwrap_or_else(|_| Override::empty())
+}
+
+fn build_overrides(path: &PathBuf, keep: Option<&HashSet<PathBuf>>) -> Result<Override, Fault> {
+    let mut builder = OverrideBuilder::new(path);
+
+    let include_file = path.join(ORBIT_INCLUDE_FILE);
+    if include_file.is_file() {
+        for line in std::fs::read_to_string(&include_file)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            builder.add(line)?;
+        }
+    }
+
+    if let Some(keep) = keep {
+        for p in keep {
+            if let Ok(rel) = p.strip_prefix(path) {
+                builder.add(&format!("/{}", into_std_str(rel.to_path_buf())))?;
+            }
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Determines how many threads a directory walk should use.
+///
+/// Defaults to the machine's available parallelism, unless
+/// [environment::ORBIT_SINGLE_THREADED] is set, in which case the walk falls
+/// back to a single thread (useful for debugging non-deterministic results).
+pub fn default_thread_count() -> usize {
+    if environment::is_single_threaded() == true {
+        1
+    } else {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    }
+}
+
 /// Recursively walks the given `path` and ignores files defined in a .gitignore file or .orbitignore files.
 ///
 /// Returns the resulting list of filepath strings. This function silently skips result errors
@@ -21,47 +132,105 @@ use super::anyerror::Fault;
 /// final [String] entries in the resulting vector.
 ///
 /// Ignores ORBIT_SUM_FILE, .git directory, ORBIT_METADATA_FILE, and IP_LOCK_FILE.
-pub fn gather_current_files(path: &PathBuf, strip_base: bool) -> Vec<String> {
-    let m = WalkBuilder::new(path)
+///
+/// The walk is parallelized across `threads` worker threads (see
+/// [default_thread_count]); the result is still fully sorted afterward so the
+/// output remains reproducible regardless of how many threads were used.
+///
+/// `file_types` declares extra/overriding glob patterns (see [filetype]) for
+/// the named registry; since no selection is applied here, every file is
+/// still collected regardless of its type.
+///
+/// An [ORBIT_INCLUDE_FILE] at the root of `path`, if present, force-includes
+/// any paths it lists even if an `.orbitignore` pattern would otherwise drop
+/// them (see [resolve_overrides]).
+///
+/// A path with a non-UTF-8 component cannot be represented as a [String];
+/// such entries are skipped (and reported) individually rather than
+/// panicking or aborting the rest of the walk.
+pub fn gather_current_files(
+    path: &PathBuf,
+    strip_base: bool,
+    threads: usize,
+    file_types: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let walker = WalkBuilder::new(path)
         .hidden(false)
+        .threads(threads)
+        .types(resolve_types(file_types))
+        .overrides(resolve_overrides(path, None))
         .add_custom_ignore_filename(ORBIT_IGNORE_FILE)
-        .filter_entry(|p| match p.file_name().to_str().unwrap() {
-            manifest::ORBIT_SUM_FILE | lockfile::IP_LOCK_FILE | manifest::ORBIT_METADATA_FILE => {
-                false
-            }
-            _ => true,
+        .filter_entry(|p| {
+            let name = p.file_name().container_as_bytes();
+            name.as_ref() != manifest::ORBIT_SUM_FILE.as_bytes()
+                && name.as_ref() != lockfile::IP_LOCK_FILE.as_bytes()
+                && name.as_ref() != manifest::ORBIT_METADATA_FILE.as_bytes()
         })
-        .build();
-    let mut files: Vec<String> = m
-        .filter_map(|result| {
-            match result {
-                Ok(entry) => {
-                    if entry.path().is_file() {
-                        // perform standardization
-                        Some(into_std_str(match strip_base {
-                            true => remove_base(&path, &entry.into_path()),
-                            false => entry.into_path(),
-                        }))
-                    } else {
-                        None
+        .build_parallel();
+
+    let files: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    walker.run(|| {
+        Box::new(|result| {
+            if let Ok(entry) = result {
+                if entry.path().is_file() {
+                    let full = match strip_base {
+                        true => remove_base(&path, &entry.into_path()),
+                        false => entry.into_path(),
+                    };
+                    // perform standardization, skipping paths that cannot
+                    // be represented as valid UTF-8
+                    match try_into_std_str(&full) {
+                        Some(file) => files.lock().unwrap().push(file),
+                        None => println!(
+                            "info: skipping {:?}: path contains non-UTF-8 characters",
+                            full
+                        ),
                     }
                 }
-                Err(_) => None,
             }
+            ignore::WalkState::Continue
         })
-        .collect();
+    });
+
+    let mut files = files.into_inner().unwrap();
     // sort the fileset for reproducibility purposes
     files.sort();
     files
 }
 
+/// Replaces '\' characters with a single '/' character.
+///
+/// Operates on the path's raw bytes (see [BytesContainer]) rather than
+/// requiring the path be valid UTF-8 up front; this is the final display
+/// boundary, so the result is a lossy UTF-8 conversion of whatever bytes
+/// aren't representable.
+fn standardize_separators(bytes: &[u8]) -> String {
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    for &b in bytes {
+        out.push(if b == b'\\' { b'/' } else { b });
+    }
+    if out.last() == Some(&b'/') {
+        out.pop();
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
 /// Replaces '\' characters with single '/' character and converts the [PathBuf] into a [String].
+///
+/// This is a display-purpose, lossy conversion: any non-UTF-8 bytes in
+/// `path` are replaced with the Unicode replacement character rather than
+/// panicking. Prefer [try_into_std_str] when the caller needs to detect
+/// and react to a non-representable path instead of silently lossifying it.
 pub fn into_std_str(path: PathBuf) -> String {
-    let mut s = path.display().to_string().replace(r"\", "/");
-    if s.ends_with("/") == true {
-        s.pop().unwrap();
-    }
-    s
+    standardize_separators(path.as_os_str().container_as_bytes().as_ref())
+}
+
+/// Like [into_std_str], but returns `None` instead of lossily mangling a
+/// path that isn't valid UTF-8, so the caller can skip or report it.
+fn try_into_std_str(path: &Path) -> Option<String> {
+    let bytes = path.as_os_str().container_as_bytes();
+    std::str::from_utf8(bytes.as_ref()).ok()?;
+    Some(standardize_separators(bytes.as_ref()))
 }
 
 pub enum Unit {
@@ -161,16 +330,6 @@ pub fn is_orbit_metadata(s: &str) -> bool {
     s == manifest::IP_MANIFEST_FILE || s == ORBIT_IGNORE_FILE || s == lockfile::IP_LOCK_FILE
 }
 
-pub fn is_minimal(name: &str) -> bool {
-    fileset::is_vhdl(&name) == true || is_orbit_metadata(&name) == true
-}
-
-pub fn is_keep_override(target: &PathBuf, vip_list: &Vec<PathBuf>) -> bool {
-    println!("{:?}", target);
-    println!("{:?}", vip_list);
-    vip_list.iter().find(|&p| p == target).is_some()
-}
-
 /// Recursively copies files from `source` to `target` directory.
 ///
 /// Assumes `target` directory does not already exist. Ignores the `.git/` folder
@@ -178,30 +337,70 @@ pub fn is_keep_override(target: &PathBuf, vip_list: &Vec<PathBuf>) -> bool {
 ///
 /// If immutable is `true`, then read_only permissions will be enabled, else the files
 /// will be mutable. Silently skips files that could be changed with mutability/permissions.
-pub fn copy(source: &PathBuf, target: &PathBuf, minimal: bool, keep: Option<Vec<PathBuf>>) -> Result<(), Fault> {
+///
+/// `selected_types` restricts the copy to files matching one of the named
+/// [filetype] categories (plus Orbit's own metadata files, which are always
+/// carried over); `None` copies everything. `file_types` declares
+/// extra/overriding glob patterns for those categories, layered on top of
+/// [filetype]'s defaults.
+///
+/// Every path in `keep` is force-included via the same allowlist mechanism
+/// as [ORBIT_INCLUDE_FILE] (see [resolve_overrides]), so a selected type or
+/// an `.orbitignore` pattern can never drop a file the caller explicitly
+/// needs kept.
+///
+/// The walk is parallelized across `threads` worker threads (see
+/// [default_thread_count]); the gathered paths are sorted afterward so the
+/// directory-creation and empty-directory-removal passes below still see
+/// parents before their children, matching the single-threaded behavior.
+pub fn copy(
+    source: &PathBuf,
+    target: &PathBuf,
+    selected_types: Option<&[&str]>,
+    keep: Option<HashSet<PathBuf>>,
+    threads: usize,
+    file_types: &HashMap<String, Vec<String>>,
+) -> Result<(), Fault> {
     // create missing directories to `target`
     std::fs::create_dir_all(&target)?;
     // gather list of paths to copy
-    let mut from_paths = Vec::new();
+    let from_paths: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+    let selector = match selected_types {
+        Some(names) => Some(filetype::build_selected_types(file_types, names)?),
+        None => None,
+    };
+    let overrides = resolve_overrides(source, keep.as_ref());
+    let kept_matcher = overrides.clone();
 
     // respect .orbitignore by using `WalkBuilder`
-    for result in WalkBuilder::new(&source)
+    let walker = WalkBuilder::new(&source)
         .hidden(false)
+        .threads(threads)
+        .types(resolve_types(file_types))
+        .overrides(overrides)
         .add_custom_ignore_filename(ORBIT_IGNORE_FILE)
-        // only capture files that are required by minimal installations
+        // only capture files that are required by minimal installations, plus
+        // anything force-included via `keep` or `.orbitinclude`
         .filter_entry(move |f| {
             f.path().is_file() == false
-                || minimal == false
-                || is_minimal(&f.file_name().to_string_lossy()) == true
-                || (keep.is_some() && is_keep_override(&f.path().to_path_buf(), &keep.as_ref().unwrap()) == true)
+                || selector.is_none()
+                || selector.as_ref().unwrap().matched(f.path(), false).is_whitelist()
+                || is_orbit_metadata(&f.file_name().to_string_lossy())
+                || kept_matcher.matched(f.path(), false).is_whitelist()
         })
-        .build()
-    {
-        match result {
-            Ok(entry) => from_paths.push(entry.path().to_path_buf()),
-            Err(_) => (),
-        }
-    }
+        .build_parallel();
+    walker.run(|| {
+        Box::new(|result| {
+            if let Ok(entry) = result {
+                from_paths.lock().unwrap().push(entry.path().to_path_buf());
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    let mut from_paths = from_paths.into_inner().unwrap();
+    from_paths.sort();
     // create all missing directories
     for from in from_paths.iter().filter(|f| f.is_dir()) {
         // replace common `source` path with `target` path
@@ -289,64 +488,69 @@ impl Standardize for PathBuf {
         // break the path into parts
         let mut parts = p.components();
 
-        let c_str = |cmp: Component| match cmp {
-            Component::RootDir => String::new(),
-            _ => String::from(cmp.as_os_str().to_str().unwrap()),
+        // component folding operates on raw bytes (see [BytesContainer]) so
+        // a non-UTF-8 component doesn't panic; only the final assembly
+        // lossily stringifies what can't be represented as UTF-8
+        let c_bytes = |cmp: Component| -> Vec<u8> {
+            match cmp {
+                Component::RootDir => Vec::new(),
+                _ => cmp.as_os_str().container_as_bytes().into_owned(),
+            }
         };
 
-        let mut result = Vec::<String>::new();
+        let mut result = Vec::<Vec<u8>>::new();
         // check first part for home path '~', absolute path, or other (relative path '.'/None)
         if let Some(root) = parts.next() {
             if root.as_os_str() == OsStr::new("~") {
                 match home_dir() {
                     Some(home) => {
                         for part in home.components() {
-                            result.push(c_str(part))
+                            result.push(c_bytes(part))
                         }
                     }
-                    None => result.push(String::from(root.as_os_str().to_str().unwrap())),
+                    None => result.push(root.as_os_str().container_as_bytes().into_owned()),
                 }
             } else if root == Component::RootDir {
-                result.push(String::from(root.as_os_str().to_str().unwrap()))
+                result.push(root.as_os_str().container_as_bytes().into_owned())
             } else {
-                // for part in std::env::current_dir().unwrap().components() { result.push(c_str(part)) }
-                match root.as_os_str().to_str().unwrap() {
-                    "." => (),
-                    ".." => {
+                // for part in std::env::current_dir().unwrap().components() { result.push(c_bytes(part)) }
+                match root.as_os_str().container_as_bytes().as_ref() {
+                    b"." => (),
+                    b".." => {
                         result.pop();
                         ()
                     }
-                    _ => result.push(String::from(root.as_os_str().to_str().unwrap())),
+                    _ => result.push(root.as_os_str().container_as_bytes().into_owned()),
                 }
             }
         }
         // push user-defined path (remaining components)
         while let Some(part) = parts.next() {
-            match part.as_os_str().to_str().unwrap() {
+            match part.as_os_str().container_as_bytes().as_ref() {
                 // do nothing; remain in the same directory
-                "." => (),
+                b"." => (),
                 // pop if using a '..'
-                ".." => {
+                b".." => {
                     result.pop();
                     ()
                 }
                 // push all other components
-                _ => result.push(c_str(part)),
+                _ => result.push(c_bytes(part)),
             }
         }
         // assemble new path
         let mut first = true;
+        let mut joined = Vec::<u8>::new();
+        for part in result {
+            if first == true {
+                first = false;
+            } else {
+                joined.push(b'/');
+            }
+            joined.extend_from_slice(&part);
+        }
         PathBuf::from(
-            result
-                .into_iter()
-                .fold(String::new(), |x, y| {
-                    if first == true {
-                        first = false;
-                        x + &y
-                    } else {
-                        x + "/" + &y
-                    }
-                })
+            String::from_utf8_lossy(&joined)
                 .replace("\\", "/")
                 .replace("//", "/"),
         )
@@ -468,7 +672,15 @@ mod test {
     fn copy_minimal() {
         let source = PathBuf::from("test/data/projects");
         let target = tempdir().unwrap();
-        copy(&source, &target.as_ref().to_path_buf(), true, None).unwrap();
+        copy(
+            &source,
+            &target.as_ref().to_path_buf(),
+            Some(filetype::MINIMAL_TYPES),
+            None,
+            default_thread_count(),
+            &HashMap::new(),
+        )
+        .unwrap();
     }
 
     // only works on windows system
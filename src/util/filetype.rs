@@ -0,0 +1,90 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A named file-type registry, modeled on the `ignore` crate's own `types`
+//! subsystem, for declaring "which files count" as a given HDL or
+//! build-artifact category without hard-coding extension checks.
+
+use std::collections::HashMap;
+
+use ignore::types::{Types, TypesBuilder};
+
+use super::anyerror::Fault;
+
+pub const VHDL: &str = "vhdl";
+pub const VERILOG: &str = "verilog";
+pub const SYSTEMVERILOG: &str = "systemverilog";
+pub const XDC: &str = "xdc";
+
+/// Type names kept by a "minimal" installation: the VHDL sources plus Orbit's
+/// own metadata files (the latter are always kept separately; see
+/// [crate::util::filesystem::is_orbit_metadata]).
+pub const MINIMAL_TYPES: &[&str] = &[VHDL];
+
+/// Built-in type-name-to-glob-pattern definitions.
+///
+/// Mirrors the extensions recognized by [crate::core::fileset::is_vhdl],
+/// [crate::core::fileset::is_verilog], and
+/// [crate::core::fileset::is_systemverilog].
+const DEFAULTS: &[(&str, &[&str])] = &[
+    (VHDL, &["*.vhd", "*.vhdl"]),
+    (VERILOG, &["*.v", "*.vl", "*.verilog", "*.vlg", "*.vh"]),
+    (SYSTEMVERILOG, &["*.sv", "*.svh"]),
+    (XDC, &["*.xdc"]),
+];
+
+/// Assembles a [TypesBuilder] seeded with [DEFAULTS], with `overrides` (e.g.
+/// read from `config.toml`'s `[filetype]` table) layered in on top. An
+/// override for a name already present in [DEFAULTS] adds to its patterns
+/// rather than replacing them.
+fn seeded_builder(overrides: &HashMap<String, Vec<String>>) -> Result<TypesBuilder, Fault> {
+    let mut builder = TypesBuilder::new();
+    for (name, patterns) in DEFAULTS {
+        for pattern in *patterns {
+            builder.add(name, pattern)?;
+        }
+    }
+    for (name, patterns) in overrides {
+        for pattern in patterns {
+            builder.add(name, pattern)?;
+        }
+    }
+    builder.add_defaults();
+    Ok(builder)
+}
+
+/// Builds a [Types] registry that matches every known type (the neutral,
+/// non-filtering case used when gathering a complete fileset).
+pub fn build_types(overrides: &HashMap<String, Vec<String>>) -> Result<Types, Fault> {
+    Ok(seeded_builder(overrides)?.build()?)
+}
+
+/// Builds a [Types] registry that matches only the named `selected` types.
+///
+/// An empty `selected` list matches nothing; callers that want "everything"
+/// should use [build_types] instead.
+pub fn build_selected_types(
+    overrides: &HashMap<String, Vec<String>>,
+    selected: &[&str],
+) -> Result<Types, Fault> {
+    let mut builder = seeded_builder(overrides)?;
+    builder.negate("all");
+    for name in selected {
+        builder.select(name);
+    }
+    Ok(builder.build()?)
+}
@@ -0,0 +1,108 @@
+//
+//  Copyright (C) 2022-2024  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the advisory lock file placed at the root of a guarded directory.
+pub const LOCK_FILE: &str = ".orbit-lock";
+
+/// An RAII guard holding an advisory, whole-file lock on a directory's
+/// `.orbit-lock` file for as long as it stays alive, so two `orbit`
+/// processes don't race while downloading into or installing to the same
+/// cache. The lock is released when the guard is dropped.
+pub struct CacheLock {
+    _file: File,
+    path: PathBuf,
+}
+
+impl CacheLock {
+    /// Blocks until the lock on `dir`'s [LOCK_FILE] can be acquired.
+    pub fn acquire(dir: &Path) -> Result<Self, io::Error> {
+        Self::open_and_lock(dir, true)
+    }
+
+    /// Attempts to acquire the lock on `dir`'s [LOCK_FILE] without blocking,
+    /// returning `Ok(None)` if another process already holds it.
+    ///
+    /// Used under `--locked`/`--frozen`, where waiting on another `orbit`
+    /// process to finish mutating the cache would contradict the promise
+    /// that nothing observable changes during this run.
+    pub fn try_acquire(dir: &Path) -> Result<Option<Self>, io::Error> {
+        match Self::open_and_lock(dir, false) {
+            Ok(lock) => Ok(Some(lock)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn open_and_lock(dir: &Path, blocking: bool) -> Result<Self, io::Error> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(LOCK_FILE);
+        let file = OpenOptions::new().create(true).write(true).open(&path)?;
+        sys::lock(&file, blocking)?;
+        Ok(Self { _file: file, path })
+    }
+
+    pub fn get_path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    extern "C" {
+        fn flock(fd: i32, operation: i32) -> i32;
+    }
+
+    const LOCK_EX: i32 = 2;
+    const LOCK_NB: i32 = 4;
+
+    pub fn lock(file: &File, blocking: bool) -> Result<(), io::Error> {
+        let op = if blocking == true { LOCK_EX } else { LOCK_EX | LOCK_NB };
+        let rc = unsafe { flock(file.as_raw_fd(), op) };
+        if rc == 0 {
+            Ok(())
+        } else {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                // EWOULDBLOCK
+                Some(11) => Err(io::Error::new(io::ErrorKind::WouldBlock, err)),
+                _ => Err(err),
+            }
+        }
+    }
+}
+
+/// Non-unix targets have no `flock` equivalent wired up yet; the lock file
+/// is still created so its presence is meaningful to unix peers, but this
+/// process does not itself enforce mutual exclusion against other
+/// processes on the same machine.
+#[cfg(not(unix))]
+mod sys {
+    use std::fs::File;
+    use std::io;
+
+    pub fn lock(_file: &File, _blocking: bool) -> Result<(), io::Error> {
+        Ok(())
+    }
+}
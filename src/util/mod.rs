@@ -1,9 +1,12 @@
 pub mod anyerror;
+pub mod archive;
 pub mod checksum;
 pub mod environment;
 pub mod filesystem;
+pub mod filetype;
 pub mod graph;
 pub mod graphmap;
+pub mod lock;
 pub mod overdetsys;
 pub mod prompt;
 pub mod seqalin;
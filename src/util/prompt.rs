@@ -15,10 +15,11 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use crate::util::environment;
 use colored::ColoredString;
 use colored::Colorize;
 use std::io;
-use std::io::{Error, Read};
+use std::io::{Error, IsTerminal, Read};
 
 /// Conditionally operates on `status` to return an string representation.
 pub fn report_eval(status: bool) -> ColoredString {
@@ -28,23 +29,45 @@ pub fn report_eval(status: bool) -> ColoredString {
     }
 }
 
-/// Outputs the text `s` with a ? mark and y/n option. Accepts '\n' or
-/// 'y' to return `true`, and `n` to return `false`.
+/// Outputs the text `s` with a ? mark and a y/n option. An empty line maps
+/// to `true`.
+///
+/// Returns immediately without touching stdin when running
+/// non-interactively; see [prompt_with_default].
 pub fn prompt(s: &str) -> Result<bool, Error> {
-    println!("{}? [y/n]", s);
-    check_for_response(&mut io::stdin().lock())
+    prompt_with_default(s, true)
+}
+
+/// Like [prompt], but lets the caller choose what an empty line resolves
+/// to, rendering `[Y/n]` or `[y/N]` to match.
+///
+/// Returns `true` immediately without reading from stdin when `--yes` was
+/// given (see [environment::is_assume_yes]). Returns `default` immediately
+/// without reading from stdin when running non-interactively (see
+/// [environment::is_non_interactive]) or when stdin is not a tty.
+pub fn prompt_with_default(s: &str, default: bool) -> Result<bool, Error> {
+    if environment::is_assume_yes() == true {
+        return Ok(true);
+    }
+    if environment::is_non_interactive() == true || io::stdin().is_terminal() == false {
+        return Ok(default);
+    }
+    let options = if default == true { "[Y/n]" } else { "[y/N]" };
+    println!("{}? {}", s, options);
+    check_for_response(&mut io::stdin().lock(), default)
 }
 
-/// Infinitely loops until a valid response is entered. "Y\n" and "\n" map to `true`, while
-/// "N\n" maps to `false`.
+/// Infinitely loops until a valid response is entered. "Y\n" maps to `true`,
+/// "N\n" maps to `false`, and an empty line maps to `default`.
 ///
 /// Also supports checking windows-style line endings `\r\n`.
-fn check_for_response(input: &mut (impl Read + std::io::BufRead)) -> Result<bool, Error> {
+fn check_for_response(input: &mut (impl Read + std::io::BufRead), default: bool) -> Result<bool, Error> {
     let mut buffer: String = String::new();
     loop {
         input.read_line(&mut buffer)?;
         let result = match buffer.to_uppercase().as_ref() {
-            "\r\n" | "\n" | "Y\n" | "Y\r\n" => Some(true),
+            "\r\n" | "\n" => Some(default),
+            "Y\n" | "Y\r\n" => Some(true),
             "N\n" | "N\r\n" => Some(false),
             _ => {
                 buffer.clear();
@@ -63,25 +86,33 @@ mod test {
 
     #[test]
     fn example_input_to_output() {
-        let r = check_for_response(&mut "n\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "n\n".as_bytes(), true).unwrap();
         assert_eq!(r, false);
-        let r = check_for_response(&mut "N\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "N\n".as_bytes(), true).unwrap();
         assert_eq!(r, false);
-        let r = check_for_response(&mut "\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "\n".as_bytes(), true).unwrap();
         assert_eq!(r, true);
-        let r = check_for_response(&mut "Y\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "Y\n".as_bytes(), true).unwrap();
         assert_eq!(r, true);
-        let r = check_for_response(&mut "y\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "y\n".as_bytes(), true).unwrap();
         assert_eq!(r, true);
     }
 
     #[test]
     fn windows_style() {
-        let r = check_for_response(&mut "y\r\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "y\r\n".as_bytes(), true).unwrap();
         assert_eq!(r, true);
-        let r = check_for_response(&mut "\r\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "\r\n".as_bytes(), true).unwrap();
         assert_eq!(r, true);
-        let r = check_for_response(&mut "N\r\n".as_bytes()).unwrap();
+        let r = check_for_response(&mut "N\r\n".as_bytes(), true).unwrap();
+        assert_eq!(r, false);
+    }
+
+    #[test]
+    fn empty_line_resolves_to_given_default() {
+        let r = check_for_response(&mut "\n".as_bytes(), false).unwrap();
+        assert_eq!(r, false);
+        let r = check_for_response(&mut "\r\n".as_bytes(), false).unwrap();
         assert_eq!(r, false);
     }
 }